@@ -5,12 +5,30 @@ mod service;
 mod store;
 
 pub use logic::desc_director::DescDirector;
+pub use logic::desc_query::DescQuery;
+pub use logic::desc_format::{DescFormatter, OutputFormat};
 pub use misc::descriptor_tools;
 pub use model::descriptor::Descriptor;
-pub use model::space::Space;
-pub use model::app::App;
+pub use model::desc_field::DescField;
+pub use model::space_id::SpaceId;
+pub use model::app_name::AppName;
+pub use model::desc_error::DescError;
+pub use model::point_policy::PointPolicy;
+pub use model::point_matching::PointMatching;
+pub use model::space_info::{SpaceInfo, SpaceSummary};
+pub use model::space_stats::SpaceStats;
+pub use model::space_descriptor::{SpaceDescriptor, SpacePrecedence};
+pub use model::space_diff::{DescChange, SpaceDiff};
+pub use model::space_merge::{MergeConflict, MergeReport, MergeResolution, MergeStrategy};
+pub use model::search_hit::SearchHit;
+pub use model::suggestion::Suggestion;
+pub use model::completion::Completion;
+pub use model::list_options::{ListOptions, ListRow, SortOrder};
+pub use model::regex_hit::{RegexHit, RegexOptions};
+pub use model::duplicate_cluster::DuplicateCluster;
 pub use service::desc_service_fs;
 pub use store::descriptor_facade;
 pub use store::descriptor_store;
 pub use store::descriptor_store_fs;
+pub use store::space_guard;
 
@@ -1,25 +1,34 @@
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use crate::{Descriptor, descriptor_facade::DescriptorFacade, descriptor_store::DescriptorStore};
+use crate::space_guard::SpaceGuard;
+use crate::misc::{descriptor_tools, diff_tools, similarity_tools};
+
+use crate::{Completion, DescFormatter, Descriptor, OutputFormat, ListOptions, ListRow, RegexHit, RegexOptions, DescField, DuplicateCluster, DescError, DescQuery, MergeReport, SearchHit, MergeStrategy, PointMatching, PointPolicy, SpaceDescriptor, SpaceDiff, SpaceId, SpaceInfo, SpacePrecedence, SpaceStats, SpaceSummary, Suggestion, descriptor_facade::DescriptorFacade, descriptor_store::DescriptorStore};
 
 #[derive(Clone)]
 pub struct DescDirector <T:DescriptorStore> {
    descriptors: DescriptorFacade<T>,
+   /// Row numbers handed out by listings of the space in use, mapped to their desc_ids.
+   row_handles: Arc<Mutex<HashMap<usize, String>>>,
 }
 
 impl<T:DescriptorStore> DescDirector<T> {
 
     pub fn new(descriptors: DescriptorFacade<T>) -> Self {
-        DescDirector{descriptors}
+        DescDirector{descriptors, row_handles: Arc::new(Mutex::new(HashMap::new()))}
     }
 
     /// 
     /// Creates and saves Descriptor and indexes. 
     /// Newlines and surrounding white spaces in the single line fields are automatically filtered
-    /// out.
+    /// out. An empty label is replaced by the default label of the space.
+    ///
+    /// Fails if the point is already described and the point policy of the space rejects
+    /// competing descriptors.
     ///
-    pub fn create_desc(&self, point: String, name: String, label: String, description: String) -> Descriptor{
+    pub fn create_desc(&self, point: String, name: String, label: String, description: String) -> Result<Descriptor, DescError> {
         let mut desc = Descriptor{
             point: point.trim().replace("\n", "").replace("\r", "").to_string(),
             desc_id: "".trim().to_string(),
@@ -27,34 +36,389 @@ impl<T:DescriptorStore> DescDirector<T> {
             label: label.trim().replace("\n", "").replace("\r", "").to_string(),
             description: description.trim().to_string(),
         };
-        let desc_id = self.descriptors.add_desc(desc.clone());
-        desc.set_desc_id(&desc_id);
-        self.descriptors.add_desc_index(desc.clone());
-        desc
+        if desc.label.is_empty() {
+            desc.set_label(&self.descriptors.get_current_space_info().default_label);
+        }
+        self.descriptors.add_desc_n_index(desc)
     }
 
     ///
     /// Returns a list with all descriptor notes. 
     ///
     pub fn ls_descriptor_notes(&self) -> String {
-        let descs = self.descriptors.get_all_descs();
-        descs.iter().enumerate().map(|(c,d)| format!("{}: {} {} {} {}\n",c, d.point, d.name, d.label, d.description))
-            .reduce(|mut result, var| { result.push_str(&var); result}).unwrap()
+        self.ls_formatted(&ListOptions::default(), &OutputFormat::Plain)
+    }
+
+    ///
+    /// Returns a page of descriptor notes written by the given formatter, e.g. an OutputFormat.
+    ///
+    pub fn ls_formatted(&self, options: &ListOptions, formatter: &dyn DescFormatter) -> String {
+        formatter.format(&self.list(options))
+    }
+
+    ///
+    /// Returns a sorted, filtered page of descriptor notes with their row numbers. The rows are
+    /// remembered, so get_desc_ls_line_number keeps resolving them to the listed notes even
+    /// when the space changes afterwards.
+    ///
+    pub fn list(&self, options: &ListOptions) -> Vec<ListRow> {
+        let rows = self.descriptors.list_descs(options);
+        let mut handles = self.row_handles.lock().unwrap_or_else(|x| x.into_inner());
+        rows.iter().for_each(|x| {
+            handles.insert(x.row, x.desc.desc_id.clone());
+        });
+        rows
+    }
+
+    ///
+    /// Returns the desc_id of the descriptor note listed with the given row number.
+    ///
+    pub fn get_desc_ls_line_number(&self, line_number: String) -> Result<String, DescError> {
+        let row = line_number.trim().parse::<usize>().map_err(|_| DescError::InvalidRows(line_number.clone()))?;
+        self.get_desc_ids_for_row_list(&[row]).map(|mut x| x.remove(0))
     }
 
     ///
-    /// Returns a list with all descriptor notes annotated with line numbers.
+    /// Returns the desc_ids for listing rows given like `3-7,9`, for bulk operations.
     ///
-    pub fn get_desc_ls_line_number(&self, line_number: String) -> String {
-        let descs: Vec<String> = self.descriptors.get_all_desc_ids();
-        let desc: Option<String> = descs.get(line_number.parse::<usize>().unwrap()).cloned();
-        desc.unwrap_or("".to_string()).to_string()
+    pub fn get_desc_ids_for_rows(&self, rows: &str) -> Result<Vec<String>, DescError> {
+        let listed = self.row_handles.lock().unwrap_or_else(|x| x.into_inner()).keys().max().map_or(0, |x| x + 1);
+        let row_count = listed.max(self.descriptors.get_all_desc_ids().len());
+        self.get_desc_ids_for_row_list(&descriptor_tools::parse_row_ranges(rows, row_count)?)
     }
 
     ///
-    /// Returns a HashMap where the entry values are Descriptor Notes and their points are the keys.
+    /// Resolves rows handed out by earlier listings first. Rows that were not listed yet are
+    /// looked up by their position in the unsorted listing of the space.
+    ///
+    fn get_desc_ids_for_row_list(&self, rows: &[usize]) -> Result<Vec<String>, DescError> {
+        let handles = self.row_handles.lock().unwrap_or_else(|x| x.into_inner()).clone();
+        let mut all_ids: Option<Vec<String>> = None;
+        rows.iter()
+            .map(|row| match handles.get(row) {
+                Some(id) => Ok(id.clone()),
+                None => all_ids
+                    .get_or_insert_with(|| self.descriptors.get_all_desc_ids())
+                    .get(*row)
+                    .cloned()
+                    .ok_or(DescError::RowNotFound(*row)),
+            })
+            .collect()
+    }
+
     ///
-    pub fn get_descs_hashmap_for_list(&self, list: Vec<String>) -> HashMap<String, Descriptor> {
+    /// Returns a HashMap from every requested point to all its Descriptor Notes, so notes sharing
+    /// a point are all kept.
+    ///
+    pub fn get_descs_hashmap_for_list(&self, list: Vec<String>) -> HashMap<String, Vec<Descriptor>> where T: Sync {
         self.descriptors.get_descs_hashmap_for_list(list)
     }
+
+    ///
+    /// Full-text search over the names, labels and descriptions of the visible Descriptor
+    /// Notes. Hits are ranked by BM25, best first, and carry a snippet with the matches marked.
+    /// The search index is cached until the indexes of the space change.
+    ///
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        self.descriptors.search(query)
+    }
+
+    ///
+    /// Returns the visible Descriptor Notes matching a query like
+    /// `label:person AND name:~"ada" AND NOT point:draft/*`. See DescQuery for the syntax.
+    ///
+    pub fn query(&self, query: &str) -> Result<Vec<Descriptor>, DescError> {
+        Ok(self.descriptors.query_descs(&DescQuery::parse(query)?))
+    }
+
+    ///
+    /// Returns existing points close to the given one, for offering "did you mean" when a
+    /// lookup finds nothing.
+    ///
+    pub fn suggest(&self, point: &str, max_distance: usize) -> Vec<Suggestion> {
+        self.descriptors.suggest(point, max_distance)
+    }
+
+    ///
+    /// Returns points and names starting with the prefix, with their labels. Cheap enough to
+    /// call on every keystroke.
+    ///
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<Completion> {
+        self.descriptors.complete(prefix, limit)
+    }
+
+    ///
+    /// Returns every label in use with the number of Descriptor Notes carrying it, most used
+    /// first.
+    ///
+    pub fn label_counts(&self) -> Vec<(String, usize)> {
+        self.descriptors.label_counts()
+    }
+
+    pub fn get_descs_by_label(&self, label: &str) -> Vec<Descriptor> {
+        self.descriptors.get_descs_by_label(label)
+    }
+
+    ///
+    /// Returns statistics over all Descriptor Notes stored in the space in use.
+    ///
+    pub fn get_space_stats(&self) -> SpaceStats {
+        self.descriptors.get_space_stats()
+    }
+
+    ///
+    /// Lazily loads the visible Descriptor Notes one at a time, for exports and scans over
+    /// large spaces.
+    ///
+    pub fn iter_descs(&self) -> impl Iterator<Item = Descriptor> + '_ {
+        self.descriptors.iter_descs()
+    }
+
+    pub fn iter_descs_by_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = Descriptor> + 'a {
+        self.descriptors.iter_descs_by_label(label)
+    }
+
+    pub fn iter_containing(&self, query: &str) -> impl Iterator<Item = Descriptor> + '_ {
+        self.descriptors.iter_containing(query)
+    }
+
+    ///
+    /// Lazily yields the Descriptor Notes matching a query in the DescQuery syntax.
+    ///
+    pub fn iter_query<'a>(&'a self, query: &'a DescQuery) -> impl Iterator<Item = Descriptor> + 'a {
+        self.descriptors.iter_query(query)
+    }
+
+    ///
+    /// Returns the Descriptor Notes whose field matches a regular expression, e.g. all notes
+    /// with an ISO date in the description, along with the byte ranges of the matches.
+    ///
+    pub fn search_regex(&self, field: DescField, pattern: &str, options: &RegexOptions) -> Result<Vec<RegexHit>, DescError> {
+        self.descriptors.search_regex(field, pattern, options)
+    }
+
+    ///
+    /// Groups the visible Descriptor Notes whose names and descriptions are at least threshold
+    /// similar, between 0.0 and 1.0, so curators can merge them.
+    ///
+    pub fn find_near_duplicates(&self, threshold: f64) -> Vec<DuplicateCluster> {
+        similarity_tools::find_near_duplicates(&self.descriptors.get_all_descs(), threshold)
+    }
+
+    ///
+    /// Returns all Descriptor Notes describing the point, so competing descriptions can be
+    /// compared.
+    ///
+    pub fn get_descs_for_point(&self, point: &str) -> Vec<Descriptor> {
+        self.descriptors.get_descs_for_point(point)
+    }
+
+    ///
+    /// Returns the Descriptor Note for the point preferred by the point policy of the space.
+    ///
+    pub fn get_preferred_desc(&self, point: &str) -> Option<Descriptor> {
+        self.descriptors.get_preferred_desc(point)
+    }
+
+    pub fn get_point_policy(&self) -> PointPolicy {
+        self.descriptors.get_point_policy()
+    }
+
+    pub fn set_point_policy(&self, policy: PointPolicy) -> Result<(), DescError> {
+        self.descriptors.set_point_policy(policy)
+    }
+
+    pub fn get_point_matching(&self) -> PointMatching {
+        self.descriptors.get_point_matching()
+    }
+
+    pub fn set_point_matching(&self, matching: PointMatching) -> Result<(), DescError> {
+        self.descriptors.set_point_matching(matching)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.descriptors.is_read_only()
+    }
+
+    pub fn set_tmp_space_id(&mut self, space_id: SpaceId) {
+        self.clear_row_handles();
+        self.descriptors.set_tmp_space_id(space_id)
+    }
+
+    pub fn revert_space_id(&mut self) {
+        self.clear_row_handles();
+        self.descriptors.revert_space_id()
+    }
+
+    ///
+    /// Forgets the rows of earlier listings, as they belong to the space that is left.
+    ///
+    fn clear_row_handles(&mut self) {
+        self.row_handles = Arc::new(Mutex::new(HashMap::new()));
+    }
+
+    pub fn get_space_id(&self) -> SpaceId {
+        self.descriptors.get_space_id()
+    }
+
+    pub fn get_tmp_space_id(&self) -> Option<SpaceId> {
+        self.descriptors.get_tmp_space_id()
+    }
+
+    ///
+    /// Switches the director to a temporary space until the returned guard is dropped.
+    ///
+    pub fn enter_space(&mut self, space_id: SpaceId) -> SpaceGuard<'_, Self> {
+        let previous = self.get_tmp_space_id();
+        self.set_tmp_space_id(space_id);
+        SpaceGuard::with_restore(self, previous, |target, previous| match previous {
+            Some(space_id) => target.set_tmp_space_id(space_id),
+            None => target.revert_space_id(),
+        })
+    }
+
+    ///
+    /// Runs the closure with the director switched to a temporary space. The space used before
+    /// is restored afterwards, also when the closure panics.
+    ///
+    pub fn with_space<R>(&mut self, space_id: SpaceId, f: impl FnOnce(&mut Self) -> R) -> R {
+        let mut guard = self.enter_space(space_id);
+        f(&mut guard)
+    }
+
+    ///
+    /// Resolves the point in the listed spaces. Every result is tagged with its space.
+    ///
+    pub fn find_point_in_spaces(&self, point: &str, spaces: &[SpaceId], precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descriptors.find_point_in_spaces(point, spaces, precedence)
+    }
+
+    ///
+    /// Resolves the point in every space of the app.
+    ///
+    pub fn find_point_anywhere(&self, point: &str, precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descriptors.find_point_anywhere(point, precedence)
+    }
+
+    ///
+    /// Returns Descriptor Notes of the listed spaces containing the query in any field.
+    ///
+    pub fn search_in_spaces(&self, query: &str, spaces: &[SpaceId], precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descriptors.search_in_spaces(query, spaces, precedence)
+    }
+
+    pub fn search_anywhere(&self, query: &str, precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descriptors.search_anywhere(query, precedence)
+    }
+
+    ///
+    /// Copies a Descriptor Note from one space to another, keeping its desc_id.
+    ///
+    pub fn copy_desc(&self, desc_id: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Descriptor, DescError> {
+        self.descriptors.copy_desc(desc_id, from_space, to_space)
+    }
+
+    ///
+    /// Moves a Descriptor Note from one space to another, keeping its desc_id.
+    ///
+    pub fn move_desc(&self, desc_id: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Descriptor, DescError> {
+        self.descriptors.move_desc(desc_id, from_space, to_space)
+    }
+
+    ///
+    /// Copies all Descriptor Notes of a space containing the query in any field.
+    ///
+    pub fn copy_descs(&self, query: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Vec<Descriptor>, DescError> {
+        self.descriptors.copy_descs(query, from_space, to_space)
+    }
+
+    ///
+    /// Moves all Descriptor Notes of a space containing the query in any field.
+    ///
+    pub fn move_descs(&self, query: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Vec<Descriptor>, DescError> {
+        self.descriptors.move_descs(query, from_space, to_space)
+    }
+
+    ///
+    /// Returns the Descriptor Note for the point from this space or the nearest parent space.
+    ///
+    pub fn get_inherited_desc(&self, point: &str) -> Option<SpaceDescriptor> {
+        self.descriptors.get_inherited_desc(point)
+    }
+
+    pub fn get_space_chain(&self) -> Vec<SpaceId> {
+        self.descriptors.get_space_chain()
+    }
+
+    ///
+    /// Sets the space that lookups fall back to. Writes always go to the space itself.
+    ///
+    pub fn set_space_parent(&self, space_id: &SpaceId, parent: Option<SpaceId>) -> Result<(), DescError> {
+        self.descriptors.set_space_parent(space_id, parent)
+    }
+
+    ///
+    /// Returns the points only described in space A, only in space B, and the points described
+    /// differently, with a unified diff of the changed fields.
+    ///
+    pub fn diff_spaces(&self, space_a: &SpaceId, space_b: &SpaceId) -> Result<SpaceDiff, DescError> {
+        self.descriptors.diff_spaces(space_a, space_b)
+    }
+
+    ///
+    /// Same as diff_spaces for two snapshots of Descriptor Notes, for example exported ones.
+    /// Points are compared the way the space in use compares them.
+    ///
+    pub fn diff_snapshots(&self, a: &[Descriptor], b: &[Descriptor]) -> SpaceDiff {
+        diff_tools::diff_descs(a, b, self.descriptors.get_point_matching())
+    }
+
+    ///
+    /// Merges all Descriptor Notes of the source space into the target space, resolving points
+    /// described differently on both sides with the strategy.
+    ///
+    pub fn merge_space(&self, source_space: &SpaceId, target_space: &SpaceId, strategy: MergeStrategy) -> Result<MergeReport, DescError> {
+        self.descriptors.merge_space(source_space, target_space, strategy)
+    }
+
+    ///
+    /// Returns all spaces of the app with their metadata and counts.
+    ///
+    pub fn ls_spaces(&self) -> Vec<SpaceSummary> {
+        self.descriptors.get_space_summaries()
+    }
+
+    pub fn get_space_info(&self, space_id: &SpaceId) -> Option<SpaceInfo> {
+        self.descriptors.get_space_info(space_id)
+    }
+
+    ///
+    /// Returns the metadata and settings of the space in use.
+    ///
+    pub fn get_current_space_info(&self) -> SpaceInfo {
+        self.descriptors.get_current_space_info()
+    }
+
+    ///
+    /// Replaces the metadata and settings of a space.
+    ///
+    pub fn set_space_info(&self, space_id: &SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.descriptors.set_space_info(space_id, info)
+    }
+
+    pub fn create_space(&self, space_id: SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.descriptors.create_space(space_id, info)
+    }
+
+    pub fn rename_space(&self, space_id: SpaceId, new_space_id: SpaceId) -> Result<(), DescError> {
+        self.descriptors.rename_space(space_id, new_space_id)
+    }
+
+    ///
+    /// Deletes a space and all Descriptor Notes in it. The confirmation must repeat the space id.
+    /// The space in use and spaces other spaces inherit from can not be deleted.
+    ///
+    pub fn delete_space(&self, space_id: SpaceId, confirmation: String) -> Result<(), DescError> {
+        self.descriptors.delete_space(space_id, confirmation)
+    }
 }
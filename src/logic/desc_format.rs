@@ -0,0 +1,233 @@
+use std::str::FromStr;
+use crate::{DescError, ListRow};
+
+const TABLE_DESCRIPTION_WIDTH: usize = 60;
+
+///
+/// Turns listed Descriptors into text. Implement it to add an output format of your own.
+///
+pub trait DescFormatter {
+    fn format(&self, rows: &[ListRow]) -> String;
+}
+
+///
+/// The built in output formats.
+///
+/// Plain and Table show the first line of multi-line descriptions only. Json, JsonLines and
+/// Csv keep descriptions whole, escaped by the rules of the format. Tsv and Template write line
+/// breaks and tabs in values as `\n`, `\r` and `\t`.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum OutputFormat {
+    /// One `row: point name label description` line per Descriptor.
+    #[default]
+    Plain,
+    /// Columns aligned below a header.
+    Table,
+    /// One JSON array.
+    Json,
+    /// One JSON object per line.
+    JsonLines,
+    /// Comma separated values with a header, quoted as in RFC 4180.
+    Csv,
+    /// Tab separated values with a header.
+    Tsv,
+    ///
+    /// One line per Descriptor from a template like `{point}\t{label}`. The placeholders are
+    /// {row}, {desc_id}, {point}, {name}, {label} and {description}; `\t` and `\n` in the
+    /// template stand for tab and newline.
+    ///
+    Template(String),
+}
+
+impl FromStr for OutputFormat {
+    type Err = DescError;
+
+    ///
+    /// Parses a format name as given on a command line. Anything with a placeholder is taken as
+    /// a template.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" => Ok(OutputFormat::Plain),
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "jsonl" | "jsonlines" => Ok(OutputFormat::JsonLines),
+            "csv" => Ok(OutputFormat::Csv),
+            "tsv" => Ok(OutputFormat::Tsv),
+            _ if s.contains('{') => Ok(OutputFormat::Template(s.to_string())),
+            _ => Err(DescError::UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+impl DescFormatter for OutputFormat {
+    fn format(&self, rows: &[ListRow]) -> String {
+        match self {
+            OutputFormat::Plain => rows
+                .iter()
+                .map(|x| format!("{}: {} {} {} {}\n", x.row, x.desc.point, x.desc.name, x.desc.label, first_line(&x.desc.description)))
+                .collect(),
+            OutputFormat::Table => format_table(rows),
+            OutputFormat::Json => serde_json::to_string_pretty(rows).unwrap_or_default() + "\n",
+            OutputFormat::JsonLines => rows
+                .iter()
+                .map(|x| serde_json::to_string(x).unwrap_or_default() + "\n")
+                .collect(),
+            OutputFormat::Csv => format_separated(rows, ",", csv_quote),
+            OutputFormat::Tsv => format_separated(rows, "\t", escape_control),
+            OutputFormat::Template(template) => {
+                let template = template.replace("\\t", "\t").replace("\\n", "\n");
+                rows.iter().map(|x| fill_template(&template, x) + "\n").collect()
+            }
+        }
+    }
+}
+
+fn fields(row: &ListRow) -> [String; 6] {
+    [
+        row.row.to_string(),
+        row.desc.desc_id.clone(),
+        row.desc.point.clone(),
+        row.desc.name.clone(),
+        row.desc.label.clone(),
+        row.desc.description.clone(),
+    ]
+}
+
+const FIELD_NAMES: [&str; 6] = ["row", "desc_id", "point", "name", "label", "description"];
+
+fn format_table(rows: &[ListRow]) -> String {
+    let header = ["ROW", "POINT", "NAME", "LABEL", "DESCRIPTION"];
+    let lines: Vec<[String; 5]> = rows
+        .iter()
+        .map(|x| [
+            x.row.to_string(),
+            x.desc.point.clone(),
+            x.desc.name.clone(),
+            x.desc.label.clone(),
+            truncate(&first_line(&x.desc.description), TABLE_DESCRIPTION_WIDTH),
+        ])
+        .collect();
+
+    let mut widths = header.map(|x| x.chars().count());
+    for line in lines.iter() {
+        for (width, value) in widths.iter_mut().zip(line.iter()) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+
+    let header = header.map(String::from);
+    std::iter::once(&header)
+        .chain(lines.iter())
+        .map(|line| {
+            let cells: Vec<String> = line
+                .iter()
+                .zip(widths.iter())
+                .map(|(value, width)| format!("{:<width$}", value, width = width))
+                .collect();
+            cells.join("  ").trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+fn format_separated(rows: &[ListRow], separator: &str, escape: fn(&str) -> String) -> String {
+    let mut result = FIELD_NAMES.join(separator) + "\n";
+    for row in rows {
+        let values: Vec<String> = fields(row).iter().map(|x| escape(x)).collect();
+        result.push_str(&values.join(separator));
+        result.push('\n');
+    }
+    result
+}
+
+///
+/// Scans the template once, so placeholders inside the values are copied as they are.
+///
+fn fill_template(template: &str, row: &ListRow) -> String {
+    let values = fields(row);
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let field = rest.find('}').and_then(|end| {
+            FIELD_NAMES.iter().position(|x| *x == &rest[1..end]).map(|x| (x, end))
+        });
+        match field {
+            Some((index, end)) => {
+                result.push_str(&escape_control(&values[index]));
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+///
+/// Returns the first line of a text, marked with an ellipsis when more lines follow.
+///
+fn first_line(text: &str) -> String {
+    let mut lines = text.lines();
+    let first = lines.next().unwrap_or("").to_string();
+    if lines.next().is_some() {
+        first + " …"
+    } else {
+        first
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    text.chars().take(width - 1).collect::<String>() + "…"
+}
+
+fn csv_quote(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_control(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\t', "\\t").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+#[test]
+fn output_format_test() {
+    let rows = vec![ListRow {
+        row: 3,
+        desc: crate::Descriptor {
+            desc_id: "id".to_string(),
+            point: "p".to_string(),
+            name: "Name, full".to_string(),
+            label: "l".to_string(),
+            description: "say \"hi\"\n\tthen go".to_string(),
+        },
+    }];
+
+    assert_eq!(OutputFormat::Plain.format(&rows), "3: p Name, full l say \"hi\" …\n");
+    assert_eq!(
+        OutputFormat::Csv.format(&rows),
+        "row,desc_id,point,name,label,description\n3,id,p,\"Name, full\",l,\"say \"\"hi\"\"\n\tthen go\"\n"
+    );
+    assert_eq!(
+        OutputFormat::Tsv.format(&rows),
+        "row\tdesc_id\tpoint\tname\tlabel\tdescription\n3\tid\tp\tName, full\tl\tsay \"hi\"\\n\\tthen go\n"
+    );
+    assert_eq!("{point}\\t{label}".parse::<OutputFormat>().unwrap().format(&rows), "p\tl\n");
+    assert_eq!("{name} {{label}}".parse::<OutputFormat>().unwrap().format(&rows), "Name, full {l}\n");
+    let mut nested = rows.clone();
+    nested[0].desc.name = "{label} of {point}".to_string();
+    assert_eq!("{name}: {label}".parse::<OutputFormat>().unwrap().format(&nested), "{label} of {point}: l\n");
+    assert_eq!(OutputFormat::JsonLines.format(&rows).lines().count(), 1);
+    assert!("yaml".parse::<OutputFormat>().is_err());
+}
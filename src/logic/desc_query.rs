@@ -0,0 +1,240 @@
+use std::iter::Peekable;
+use std::str::Chars;
+use crate::{DescError, DescField, PointMatching};
+
+///
+/// A parsed filter over the fields of Descriptors, e.g.
+/// `label:person AND name:~"ada" AND NOT point:draft/*`.
+///
+/// `field:value` matches the whole field, where `*` stands for any text and `?` for a single
+/// character. `field:~value` matches fields containing the value, ignoring case. Values holding
+/// white space, parentheses or quotes are written in double quotes. Terms are combined with AND,
+/// OR, NOT and parentheses; terms next to each other are combined with AND.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum DescQuery {
+    Term { field: DescField, value: String, contains: bool },
+    And(Box<DescQuery>, Box<DescQuery>),
+    Or(Box<DescQuery>, Box<DescQuery>),
+    Not(Box<DescQuery>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Term { field: DescField, value: String, contains: bool },
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl DescQuery {
+
+    pub fn parse(query: &str) -> Result<DescQuery, DescError> {
+        let tokens = tokenize(query)?;
+        let mut pos = 0;
+        let result = parse_or(&tokens, &mut pos)?;
+        if pos < tokens.len() {
+            return Err(DescError::QuerySyntax(format!("unexpected {:?}", tokens[pos])));
+        }
+        Ok(result)
+    }
+
+    ///
+    /// Returns the query with the values of point terms normalized by the matching, to be
+    /// evaluated against normalized points.
+    ///
+    pub fn normalize_points(&self, matching: PointMatching) -> DescQuery {
+        match self {
+            DescQuery::Term { field: DescField::Point, value, contains } => DescQuery::Term {
+                field: DescField::Point,
+                value: matching.normalize(value),
+                contains: *contains,
+            },
+            DescQuery::Term { .. } => self.clone(),
+            DescQuery::And(a, b) => DescQuery::And(Box::new(a.normalize_points(matching)), Box::new(b.normalize_points(matching))),
+            DescQuery::Or(a, b) => DescQuery::Or(Box::new(a.normalize_points(matching)), Box::new(b.normalize_points(matching))),
+            DescQuery::Not(a) => DescQuery::Not(Box::new(a.normalize_points(matching))),
+        }
+    }
+
+    ///
+    /// Evaluates the query with the values of the fields of one Descriptor.
+    ///
+    pub fn matches(&self, value_of: &dyn Fn(DescField) -> String) -> bool {
+        match self {
+            DescQuery::Term { field, value, contains: true } => {
+                value_of(*field).to_lowercase().contains(&value.to_lowercase())
+            }
+            DescQuery::Term { field, value, contains: false } => {
+                let chars: Vec<char> = value_of(*field).chars().collect();
+                let pattern: Vec<char> = value.chars().collect();
+                glob_match(&pattern, &chars)
+            }
+            DescQuery::And(a, b) => a.matches(value_of) && b.matches(value_of),
+            DescQuery::Or(a, b) => a.matches(value_of) || b.matches(value_of),
+            DescQuery::Not(a) => !a.matches(value_of),
+        }
+    }
+}
+
+fn parse_or(tokens: &[Token], pos: &mut usize) -> Result<DescQuery, DescError> {
+    let mut result = parse_and(tokens, pos)?;
+    while tokens.get(*pos) == Some(&Token::Or) {
+        *pos += 1;
+        result = DescQuery::Or(Box::new(result), Box::new(parse_and(tokens, pos)?));
+    }
+    Ok(result)
+}
+
+fn parse_and(tokens: &[Token], pos: &mut usize) -> Result<DescQuery, DescError> {
+    let mut result = parse_not(tokens, pos)?;
+    loop {
+        match tokens.get(*pos) {
+            Some(Token::And) => *pos += 1,
+            Some(Token::Term { .. }) | Some(Token::Not) | Some(Token::Open) => {}
+            _ => return Ok(result),
+        }
+        result = DescQuery::And(Box::new(result), Box::new(parse_not(tokens, pos)?));
+    }
+}
+
+fn parse_not(tokens: &[Token], pos: &mut usize) -> Result<DescQuery, DescError> {
+    let token = tokens.get(*pos).cloned();
+    *pos += 1;
+    match token {
+        Some(Token::Not) => Ok(DescQuery::Not(Box::new(parse_not(tokens, pos)?))),
+        Some(Token::Term { field, value, contains }) => Ok(DescQuery::Term { field, value, contains }),
+        Some(Token::Open) => {
+            let result = parse_or(tokens, pos)?;
+            if tokens.get(*pos) != Some(&Token::Close) {
+                return Err(DescError::QuerySyntax("missing ')'".to_string()));
+            }
+            *pos += 1;
+            Ok(result)
+        }
+        Some(token) => Err(DescError::QuerySyntax(format!("unexpected {:?}", token))),
+        None => Err(DescError::QuerySyntax("unexpected end of query".to_string())),
+    }
+}
+
+fn tokenize(query: &str) -> Result<Vec<Token>, DescError> {
+    let mut tokens = Vec::new();
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.peek().copied() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' {
+            chars.next();
+            tokens.push(Token::Open);
+        } else if c == ')' {
+            chars.next();
+            tokens.push(Token::Close);
+        } else {
+            let word = read_word(&mut chars, |c| c == ':' || c == '"');
+            if chars.peek() != Some(&':') {
+                let token = match word.as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "NOT" => Token::Not,
+                    "" => return Err(DescError::QuerySyntax("unexpected '\"'".to_string())),
+                    _ => return Err(DescError::QuerySyntax(format!("expected field:value, found '{}'", word))),
+                };
+                tokens.push(token);
+                continue;
+            }
+            chars.next();
+            let field = word.parse::<DescField>()?;
+            let contains = chars.next_if_eq(&'~').is_some();
+            let value = if chars.next_if_eq(&'"').is_some() {
+                read_quoted(&mut chars)?
+            } else {
+                read_word(&mut chars, |_| false)
+            };
+            tokens.push(Token::Term { field, value, contains });
+        }
+    }
+    Ok(tokens)
+}
+
+///
+/// Reads until white space, a parenthesis or a character the caller stops at.
+///
+fn read_word(chars: &mut Peekable<Chars>, stop: impl Fn(char) -> bool) -> String {
+    let mut word = String::new();
+    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '(' && *c != ')' && !stop(*c)) {
+        word.push(c);
+    }
+    word
+}
+
+fn read_quoted(chars: &mut Peekable<Chars>) -> Result<String, DescError> {
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(value),
+            Some('\\') => value.extend(chars.next()),
+            Some(c) => value.push(c),
+            None => return Err(DescError::QuerySyntax("missing closing '\"'".to_string())),
+        }
+    }
+}
+
+///
+/// Matches with two pointers, going back to the last `*` on a mismatch, so the time is bounded by
+/// the product of the lengths.
+///
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[test]
+fn desc_query_parse_test() {
+    let term = |field, value: &str, contains| Box::new(DescQuery::Term { field, value: value.to_string(), contains });
+    let query = DescQuery::parse(r#"label:person AND name:~"ada l" NOT point:draft/*"#).unwrap();
+    assert_eq!(query, DescQuery::And(
+        Box::new(DescQuery::And(term(DescField::Label, "person", false), term(DescField::Name, "ada l", true))),
+        Box::new(DescQuery::Not(term(DescField::Point, "draft/*", false))),
+    ));
+
+    let value_of = |field| match field {
+        DescField::Point => "people/ada".to_string(),
+        DescField::Name => "Ada Lovelace".to_string(),
+        DescField::Label => "person".to_string(),
+        DescField::Description => String::new(),
+    };
+    assert!(query.matches(&value_of));
+    assert!(DescQuery::parse("point:draft/* OR (label:person AND desc:\"\")").unwrap().matches(&value_of));
+    assert!(DescQuery::parse("label:(person").is_err());
+    assert!(DescQuery::parse("colour:red").is_err());
+    assert!(DescQuery::parse("label:person AND").is_err());
+
+    let glob = |pattern: &str, text: &str| glob_match(&pattern.chars().collect::<Vec<char>>(), &text.chars().collect::<Vec<char>>());
+    assert!(glob("a*b?d*", "axxbcdyy"));
+    assert!(glob("*", ""));
+    assert!(!glob("a*b", "acb c"));
+    assert!(!glob(&"*a".repeat(30), &"a".repeat(29)));
+}
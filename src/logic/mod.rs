@@ -1,2 +1,4 @@
 pub mod desc_director;
 
+pub mod desc_query;
+pub mod desc_format;
@@ -1,5 +1,6 @@
 
-use crate::Descriptor;
+use crate::{Descriptor, DescError};
+use std::collections::HashSet;
 use ig_tools::hashing_tools;
 
 pub fn get_desc_id(desc: &Descriptor) -> String {
@@ -58,3 +59,62 @@ pub fn create_desc_index_line(desc: &Descriptor, field :&str) -> String {
     addition.push_str(&id);
     addition.to_string()
 }
+
+///
+/// Splits an index line into its field value and the desc_id it points to.
+///
+/// The desc_id is always the last word of a line, so field values containing spaces are kept
+/// whole. Returns None for lines that are not index lines.
+///
+pub fn parse_desc_index_line(line: &str) -> Option<(&str, &str)> {
+    let (field, id) = line.trim_end().rsplit_once(' ')?;
+    if id.is_empty() {
+        return None;
+    }
+    Some((field.trim(), id))
+}
+
+///
+/// Parses listing row numbers given as a comma separated list of rows and inclusive ranges,
+/// like `3-7,9`. Rows are returned in the given order without repetitions.
+///
+/// Ranges reaching row_count or beyond fail with DescError::RowNotFound before they are expanded.
+///
+pub fn parse_row_ranges(rows: &str, row_count: usize) -> Result<Vec<usize>, DescError> {
+    let invalid = || DescError::InvalidRows(rows.to_string());
+    let mut result: Vec<usize> = Vec::new();
+    let mut seen: HashSet<usize> = HashSet::new();
+    for part in rows.split(',') {
+        let (from, to) = match part.split_once('-') {
+            Some((from, to)) => (from.trim(), to.trim()),
+            None => (part.trim(), part.trim()),
+        };
+        let from: usize = from.parse().map_err(|_| invalid())?;
+        let to: usize = to.parse().map_err(|_| invalid())?;
+        if from > to {
+            return Err(invalid());
+        }
+        if to >= row_count {
+            return Err(DescError::RowNotFound(to));
+        }
+        result.extend((from..=to).filter(|x| seen.insert(*x)));
+    }
+    Ok(result)
+}
+
+#[test]
+fn parse_row_ranges_test() {
+    assert_eq!(parse_row_ranges("3-5, 9,4", 10).unwrap(), vec![3, 4, 5, 9]);
+    assert_eq!(parse_row_ranges("0", 1).unwrap(), vec![0]);
+    assert!(parse_row_ranges("7-3", 10).is_err());
+    assert!(parse_row_ranges("a,1", 10).is_err());
+    assert!(parse_row_ranges("", 10).is_err());
+    assert_eq!(parse_row_ranges("0-99999999999", 10), Err(DescError::RowNotFound(99999999999)));
+}
+
+#[test]
+fn parse_desc_index_line_test() {
+    assert_eq!(parse_desc_index_line("Ada Lovelace 4f2a"), Some(("Ada Lovelace", "4f2a")));
+    assert_eq!(parse_desc_index_line("point 4f2a\r"), Some(("point", "4f2a")));
+    assert_eq!(parse_desc_index_line("4f2a"), None);
+}
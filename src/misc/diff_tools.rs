@@ -0,0 +1,146 @@
+
+use std::collections::{HashMap, HashSet};
+use crate::{DescChange, Descriptor, PointMatching, SpaceDiff};
+
+///
+/// Compares two collections of Descriptors by point, with points compared by the matching.
+///
+/// As desc_ids are hashes of the content, two sides describe a point the same way exactly when
+/// they hold the same desc_ids for it. Text diffs are only computed for points where they don't.
+///
+pub fn diff_descs(a: &[Descriptor], b: &[Descriptor], matching: PointMatching) -> SpaceDiff {
+    let a_points = group_by_point(a, matching);
+    let b_points = group_by_point(b, matching);
+    let b_index: HashMap<&str, &Vec<&Descriptor>> = b_points.iter().map(|x| (x.0.as_str(), &x.1)).collect();
+    let mut diff = SpaceDiff::default();
+
+    for (point, a_descs) in a_points.iter() {
+        let Some(b_descs) = b_index.get(point.as_str()) else {
+            diff.only_in_a.extend(a_descs.iter().map(|x| (*x).clone()));
+            continue;
+        };
+        let a_ids: HashSet<&str> = a_descs.iter().map(|x| x.desc_id.as_str()).collect();
+        let b_ids: HashSet<&str> = b_descs.iter().map(|x| x.desc_id.as_str()).collect();
+        if a_ids == b_ids {
+            continue;
+        }
+        let a_desc = a_descs.iter().find(|x| !b_ids.contains(x.desc_id.as_str())).unwrap_or(&a_descs[0]);
+        let b_desc = b_descs.iter().find(|x| !a_ids.contains(x.desc_id.as_str())).unwrap_or(&b_descs[0]);
+        diff.changed.push(DescChange {
+            point: a_desc.point.clone(),
+            a: (*a_desc).clone(),
+            b: (*b_desc).clone(),
+            diff: diff_desc_fields(a_desc, b_desc),
+        });
+    }
+
+    let a_keys: HashSet<&str> = a_points.iter().map(|x| x.0.as_str()).collect();
+    for (point, b_descs) in b_points.iter() {
+        if !a_keys.contains(point.as_str()) {
+            diff.only_in_b.extend(b_descs.iter().map(|x| (*x).clone()));
+        }
+    }
+    diff
+}
+
+///
+/// Returns a unified diff of the fields that differ between two Descriptors of the same point.
+///
+pub fn diff_desc_fields(a: &Descriptor, b: &Descriptor) -> String {
+    let mut result = format!("--- a/{}\n+++ b/{}\n", a.point, b.point);
+    let fields = [
+        ("name", &a.name, &b.name),
+        ("label", &a.label, &b.label),
+        ("description", &a.description, &b.description),
+    ];
+    for (field, a_value, b_value) in fields {
+        if a_value != b_value {
+            result.push_str(&format!("@@ {} @@\n", field));
+            result.push_str(&diff_lines(a_value, b_value));
+        }
+    }
+    result
+}
+
+///
+/// Line based diff of two texts. Every line of the result is prefixed with ' ' when it is in
+/// both texts, '-' when it is only in a and '+' when it is only in b.
+///
+pub fn diff_lines(a: &str, b: &str) -> String {
+    let a_lines: Vec<&str> = a.lines().collect();
+    let b_lines: Vec<&str> = b.lines().collect();
+
+    // Length of the longest common subsequence of the remaining lines from i and j onwards.
+    let mut lcs = vec![vec![0usize; b_lines.len() + 1]; a_lines.len() + 1];
+    for i in (0..a_lines.len()).rev() {
+        for j in (0..b_lines.len()).rev() {
+            lcs[i][j] = if a_lines[i] == b_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a_lines.len() || j < b_lines.len() {
+        if i < a_lines.len() && j < b_lines.len() && a_lines[i] == b_lines[j] {
+            result.push_str(&format!(" {}\n", a_lines[i]));
+            i += 1;
+            j += 1;
+        } else if i < a_lines.len() && (j == b_lines.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            result.push_str(&format!("-{}\n", a_lines[i]));
+            i += 1;
+        } else {
+            result.push_str(&format!("+{}\n", b_lines[j]));
+            j += 1;
+        }
+    }
+    result
+}
+
+///
+/// Groups Descriptors by their normalized point, keeping the order in which points first appear.
+///
+fn group_by_point(descs: &[Descriptor], matching: PointMatching) -> Vec<(String, Vec<&Descriptor>)> {
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<(String, Vec<&Descriptor>)> = Vec::new();
+    for desc in descs {
+        let point = matching.normalize(&desc.point);
+        match positions.get(&point) {
+            Some(pos) => groups[*pos].1.push(desc),
+            None => {
+                positions.insert(point.clone(), groups.len());
+                groups.push((point, vec![desc]));
+            }
+        }
+    }
+    groups
+}
+
+#[test]
+fn diff_lines_test() {
+    let diff = diff_lines("one\ntwo\nthree", "one\n2\nthree\nfour");
+    assert_eq!(diff, " one\n-two\n+2\n three\n+four\n");
+}
+
+#[test]
+fn diff_descs_matching_test() {
+    let desc = |point: &str, name: &str| Descriptor {
+        desc_id: format!("{}-{}", point, name),
+        point: point.to_string(),
+        name: name.to_string(),
+        ..Default::default()
+    };
+    let a = [desc("Café", "old")];
+    let b = [desc("cafe", "new")];
+
+    let diff = diff_descs(&a, &b, PointMatching::UnicodeFold);
+    assert!(diff.only_in_a.is_empty() && diff.only_in_b.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].point, "Café");
+
+    let diff = diff_descs(&a, &b, PointMatching::Exact);
+    assert_eq!((diff.only_in_a.len(), diff.only_in_b.len(), diff.changed.len()), (1, 1, 0));
+}
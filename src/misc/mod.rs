@@ -1 +1,5 @@
 pub mod descriptor_tools;
+pub mod diff_tools;
+pub mod search_tools;
+pub mod text_tools;
+pub mod similarity_tools;
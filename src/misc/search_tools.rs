@@ -0,0 +1,170 @@
+use std::collections::{HashMap, HashSet};
+use crate::{Descriptor, SearchHit};
+
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+const SNIPPET_CONTEXT_WORDS: usize = 6;
+
+///
+/// Inverted index over the name, label and description of a set of Descriptors. Every
+/// Descriptor is one document; terms are lowercased alphanumeric words, without stemming.
+///
+pub struct SearchIndex {
+    descs: Vec<Descriptor>,
+    postings: HashMap<String, Vec<(usize, usize)>>,
+    doc_lengths: Vec<usize>,
+    avg_doc_length: f64,
+}
+
+impl SearchIndex {
+
+    pub fn new(descs: Vec<Descriptor>) -> Self {
+        let mut postings: HashMap<String, Vec<(usize, usize)>> = HashMap::new();
+        let mut doc_lengths = Vec::with_capacity(descs.len());
+
+        for (doc, desc) in descs.iter().enumerate() {
+            let mut term_counts: HashMap<String, usize> = HashMap::new();
+            let mut length = 0;
+            for field in [&desc.name, &desc.label, &desc.description] {
+                for (term, _) in tokenize(field) {
+                    *term_counts.entry(term).or_default() += 1;
+                    length += 1;
+                }
+            }
+            for (term, count) in term_counts {
+                postings.entry(term).or_default().push((doc, count));
+            }
+            doc_lengths.push(length);
+        }
+        let avg_doc_length = if descs.is_empty() {
+            0.0
+        } else {
+            doc_lengths.iter().sum::<usize>() as f64 / descs.len() as f64
+        };
+
+        SearchIndex { descs, postings, doc_lengths, avg_doc_length }
+    }
+
+    ///
+    /// Returns the Descriptors matching any word of the query, best BM25 score first.
+    ///
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        let terms: HashSet<String> = tokenize(query).into_iter().map(|x| x.0).collect();
+        let doc_count = self.descs.len() as f64;
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for term in terms.iter() {
+            let Some(postings) = self.postings.get(term) else {
+                continue;
+            };
+            let n = postings.len() as f64;
+            let idf = ((doc_count - n + 0.5) / (n + 0.5) + 1.0).ln();
+            for (doc, tf) in postings {
+                let tf = *tf as f64;
+                let norm = 1.0 - BM25_B + BM25_B * self.doc_lengths[*doc] as f64 / self.avg_doc_length;
+                *scores.entry(*doc).or_default() += idf * tf * (BM25_K1 + 1.0) / (tf + BM25_K1 * norm);
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(doc, score)| {
+                let desc = self.descs[doc].clone();
+                let snippet = snippet(&desc, &terms);
+                SearchHit { desc, score, snippet }
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.desc.point.cmp(&b.desc.point)));
+        hits
+    }
+}
+
+///
+/// Splits a text into lowercased alphanumeric words, each with its byte range in the text.
+///
+pub fn tokenize(text: &str) -> Vec<(String, (usize, usize))> {
+    let mut tokens = Vec::new();
+    let mut start: Option<usize> = None;
+    for (pos, c) in text.char_indices() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(pos),
+            (false, Some(from)) => {
+                tokens.push((text[from..pos].to_lowercase(), (from, pos)));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(from) = start {
+        tokens.push((text[from..].to_lowercase(), (from, text.len())));
+    }
+    tokens
+}
+
+///
+/// Cuts the words around the first match out of the description, or the name or label when
+/// the description does not match, and wraps the matched words in square brackets.
+///
+fn snippet(desc: &Descriptor, terms: &HashSet<String>) -> String {
+    let fields = [&desc.description, &desc.name, &desc.label];
+    let (text, tokens, first) = fields
+        .iter()
+        .find_map(|text| {
+            let tokens = tokenize(text);
+            let first = tokens.iter().position(|x| terms.contains(&x.0))?;
+            Some((text.as_str(), tokens, first))
+        })
+        .unwrap_or_else(|| (desc.description.as_str(), tokenize(&desc.description), 0));
+    if tokens.is_empty() {
+        return String::new();
+    }
+
+    let from = first.saturating_sub(SNIPPET_CONTEXT_WORDS);
+    let to = (first + SNIPPET_CONTEXT_WORDS).min(tokens.len() - 1);
+    let mut result = String::new();
+    let mut pos = tokens[from].1.0;
+    if from > 0 {
+        result.push_str("...");
+    } else {
+        pos = 0;
+    }
+    for (term, (start, end)) in &tokens[from..=to] {
+        result.push_str(&text[pos..*start]);
+        if terms.contains(term) {
+            result.push_str(&format!("[{}]", &text[*start..*end]));
+        } else {
+            result.push_str(&text[*start..*end]);
+        }
+        pos = *end;
+    }
+    if to < tokens.len() - 1 {
+        result.push_str("...");
+    } else {
+        result.push_str(&text[pos..]);
+    }
+    result.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[test]
+fn search_index_test() {
+    let desc = |point: &str, name: &str, description: &str| Descriptor {
+        desc_id: point.to_string(),
+        point: point.to_string(),
+        name: name.to_string(),
+        label: "note".to_string(),
+        description: description.to_string(),
+    };
+    let index = SearchIndex::new(vec![
+        desc("a", "Rust", "A systems language."),
+        desc("b", "Borrowing", "Rust checks borrowing, and borrowing again, at compile time."),
+        desc("c", "Coffee", "Beans and water."),
+    ]);
+
+    let hits = index.search("Borrowing");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].snippet, "Rust checks [borrowing], and [borrowing] again, at compile time.");
+
+    let hits = index.search("rust borrowing");
+    assert_eq!(hits.iter().map(|x| x.desc.point.as_str()).collect::<Vec<&str>>(), vec!["b", "a"]);
+    assert!(index.search("tea").is_empty());
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use crate::{Descriptor, DuplicateCluster};
+use crate::misc::text_tools;
+
+const SHINGLE_SIZE: usize = 3;
+const MINHASH_BANDS: usize = 16;
+const MINHASH_ROWS: usize = 4;
+
+///
+/// Clusters Descriptors whose names and descriptions are at least threshold similar.
+///
+/// Texts are folded and cut into overlapping character shingles. MinHash signatures split into
+/// bands pick candidate pairs without comparing every pair; candidates are then compared by
+/// the exact Jaccard similarity of their shingles. Clusters are the connected groups of
+/// similar pairs, largest first. Descriptors without name and description are left out.
+///
+pub fn find_near_duplicates(descs: &[Descriptor], threshold: f64) -> Vec<DuplicateCluster> {
+    let shingles: Vec<HashSet<u64>> = descs
+        .iter()
+        .map(|x| shingle(&format!("{} {}", x.name, x.description)))
+        .collect();
+
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (pos, set) in shingles.iter().enumerate().filter(|(_, x)| !x.is_empty()) {
+        let signature = minhash(set);
+        for (band, rows) in signature.chunks(MINHASH_ROWS).enumerate() {
+            buckets.entry((band, hash_of(rows))).or_default().push(pos);
+        }
+    }
+
+    let mut parents: Vec<usize> = (0..descs.len()).collect();
+    let mut best: HashMap<usize, f64> = HashMap::new();
+    let mut compared: HashSet<(usize, usize)> = HashSet::new();
+    for members in buckets.values() {
+        for (i, a) in members.iter().enumerate() {
+            for b in members[i + 1..].iter() {
+                if descs[*a].desc_id == descs[*b].desc_id || !compared.insert((*a, *b)) {
+                    continue;
+                }
+                let similarity = jaccard(&shingles[*a], &shingles[*b]);
+                if similarity >= threshold {
+                    let merged = [find(&mut parents, *a), find(&mut parents, *b)]
+                        .iter()
+                        .filter_map(|root| best.remove(root))
+                        .fold(similarity, f64::max);
+                    let root = union(&mut parents, *a, *b);
+                    best.insert(root, merged);
+                }
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<Descriptor>> = HashMap::new();
+    for (pos, desc) in descs.iter().enumerate() {
+        let root = find(&mut parents, pos);
+        groups.entry(root).or_default().push(desc.clone());
+    }
+    let mut clusters: Vec<DuplicateCluster> = groups
+        .into_iter()
+        .filter(|(_, descs)| descs.len() > 1)
+        .map(|(root, descs)| DuplicateCluster { descs, similarity: best.get(&root).copied().unwrap_or(0.0) })
+        .collect();
+    clusters.sort_by(|a, b| {
+        b.descs.len().cmp(&a.descs.len()).then_with(|| a.descs[0].point.cmp(&b.descs[0].point))
+    });
+    clusters
+}
+
+fn shingle(text: &str) -> HashSet<u64> {
+    let words = text_tools::fold(text).split_whitespace().collect::<Vec<&str>>().join(" ");
+    let chars: Vec<char> = words.chars().collect();
+    if chars.is_empty() {
+        return HashSet::new();
+    }
+    if chars.len() <= SHINGLE_SIZE {
+        return HashSet::from([hash_of(&chars)]);
+    }
+    chars.windows(SHINGLE_SIZE).map(hash_of).collect()
+}
+
+fn minhash(shingles: &HashSet<u64>) -> Vec<u64> {
+    (0..MINHASH_BANDS * MINHASH_ROWS)
+        .map(|seed| shingles.iter().map(|x| hash_of(&(seed, x))).min().unwrap_or(0))
+        .collect()
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn find(parents: &mut [usize], pos: usize) -> usize {
+    let mut root = pos;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut pos = pos;
+    while parents[pos] != root {
+        let next = parents[pos];
+        parents[pos] = root;
+        pos = next;
+    }
+    root
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) -> usize {
+    let root_a = find(parents, a);
+    let root_b = find(parents, b);
+    parents[root_b] = root_a;
+    root_a
+}
+
+#[test]
+fn find_near_duplicates_test() {
+    let desc = |id: &str, name: &str, description: &str| Descriptor {
+        desc_id: id.to_string(),
+        point: id.to_string(),
+        name: name.to_string(),
+        label: String::new(),
+        description: description.to_string(),
+    };
+    let descs = vec![
+        desc("1", "Ada Lovelace", "Mathematician who wrote the first published program."),
+        desc("2", "Ada Lovelace", "Mathematician who wrote the first published programme."),
+        desc("3", "Alan Turing", "Pioneer of theoretical computer science."),
+        desc("4", "ada lovelace", "Mathematician, wrote the first published program."),
+        desc("5", "", ""),
+        desc("6", " ", ""),
+    ];
+
+    let clusters = find_near_duplicates(&descs, 0.7);
+    assert_eq!(clusters.len(), 1);
+    let mut ids: Vec<&str> = clusters[0].descs.iter().map(|x| x.desc_id.as_str()).collect();
+    ids.sort();
+    assert_eq!(ids, vec!["1", "2", "4"]);
+    assert!(clusters[0].similarity >= 0.7);
+}
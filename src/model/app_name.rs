@@ -0,0 +1,76 @@
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::DescError;
+use super::space_id::validate_folder_name;
+
+///
+/// Name of the application owning the data. It is used as the app folder name, so it follows
+/// the same rules as a SpaceId.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AppName(String);
+
+impl AppName {
+    pub fn new(name: impl Into<String>) -> Result<Self, DescError> {
+        let name = name.into();
+        validate_folder_name(&name)?;
+        Ok(AppName(name))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+///
+/// "infospace" is used as a catch all app folder.
+///
+impl Default for AppName {
+    fn default() -> Self {
+        AppName("infospace".to_string())
+    }
+}
+
+impl TryFrom<String> for AppName {
+    type Error = DescError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        AppName::new(s)
+    }
+}
+
+impl TryFrom<&str> for AppName {
+    type Error = DescError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        AppName::new(s)
+    }
+}
+
+impl FromStr for AppName {
+    type Err = DescError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AppName::new(s)
+    }
+}
+
+impl From<AppName> for String {
+    fn from(name: AppName) -> String {
+        name.0
+    }
+}
+
+impl AsRef<str> for AppName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AppName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
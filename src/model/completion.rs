@@ -0,0 +1,9 @@
+///
+/// A visible Descriptor whose point or name starts with the typed prefix.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    pub point: String,
+    pub name: String,
+    pub label: String,
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+
+///
+/// Errors surfaced by the descriptor store, facade and director when an operation can not be
+/// carried out.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum DescError {
+    ///
+    /// The point is already described in a space where the point policy rejects competing
+    /// descriptors.
+    ///
+    PointAlreadyDescribed { point: String, desc_id: String },
+    /// The store was opened read-only or the space is marked read-only.
+    ReadOnly(String),
+    /// No Descriptor with the given desc_id is stored in the space.
+    DescNotFound(String),
+    /// A space id or app name that can not be used as a folder name.
+    InvalidName { name: String, reason: String },
+    /// No space with the given id exists for the app.
+    SpaceNotFound(String),
+    /// A space with the given id already exists for the app.
+    SpaceAlreadyExists(String),
+    /// The space is the one currently used by the store, or the parent of another space, and
+    /// can not be renamed or deleted.
+    SpaceInUse(String),
+    /// The parent would make the space inherit from itself.
+    InheritanceCycle(String),
+    /// The confirmation given for a destructive space operation did not match the space id.
+    ConfirmationMismatch(String),
+    /// A query could not be parsed.
+    QuerySyntax(String),
+    /// A regular expression could not be compiled.
+    InvalidPattern(String),
+    /// Row numbers or ranges of a listing could not be parsed.
+    InvalidRows(String),
+    /// The row is not part of the listing.
+    RowNotFound(usize),
+    /// No output format with the given name exists.
+    UnknownFormat(String),
+    /// The file system refused an operation.
+    Io(String),
+    /// The configuration file could not be read.
+    Config(String),
+}
+
+impl fmt::Display for DescError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DescError::PointAlreadyDescribed { point, desc_id } => {
+                write!(f, "point '{}' is already described by {}", point, desc_id)
+            }
+            DescError::ReadOnly(space) => write!(f, "space '{}' is read-only", space),
+            DescError::DescNotFound(desc_id) => write!(f, "descriptor {} does not exist", desc_id),
+            DescError::InvalidName { name, reason } => {
+                write!(f, "'{}' is not a valid name: {}", name, reason)
+            }
+            DescError::SpaceNotFound(space) => write!(f, "space '{}' does not exist", space),
+            DescError::SpaceAlreadyExists(space) => write!(f, "space '{}' already exists", space),
+            DescError::SpaceInUse(space) => write!(f, "space '{}' is in use", space),
+            DescError::InheritanceCycle(space) => {
+                write!(f, "space '{}' would inherit from itself", space)
+            }
+            DescError::ConfirmationMismatch(space) => {
+                write!(f, "confirmation does not match space '{}'", space)
+            }
+            DescError::QuerySyntax(msg) => write!(f, "invalid query: {}", msg),
+            DescError::InvalidPattern(msg) => write!(f, "invalid pattern: {}", msg),
+            DescError::InvalidRows(rows) => write!(f, "'{}' are no valid rows", rows),
+            DescError::RowNotFound(row) => write!(f, "row {} is not listed", row),
+            DescError::UnknownFormat(name) => write!(f, "unknown output format '{}'", name),
+            DescError::Io(msg) => write!(f, "io error: {}", msg),
+            DescError::Config(msg) => write!(f, "invalid configuration: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for DescError {}
+
+impl From<std::io::Error> for DescError {
+    fn from(err: std::io::Error) -> Self {
+        DescError::Io(err.to_string())
+    }
+}
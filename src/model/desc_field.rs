@@ -0,0 +1,44 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::DescError;
+
+///
+/// The searchable fields of a Descriptor, each backed by its own index.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DescField {
+    Point,
+    Name,
+    Label,
+    Description,
+}
+
+impl DescField {
+    pub const ALL: [DescField; 4] = [DescField::Point, DescField::Name, DescField::Label, DescField::Description];
+}
+
+impl fmt::Display for DescField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DescField::Point => "point",
+            DescField::Name => "name",
+            DescField::Label => "label",
+            DescField::Description => "description",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for DescField {
+    type Err = DescError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "point" => Ok(DescField::Point),
+            "name" => Ok(DescField::Name),
+            "label" => Ok(DescField::Label),
+            "description" | "desc" => Ok(DescField::Description),
+            _ => Err(DescError::QuerySyntax(format!("unknown field '{}'", s))),
+        }
+    }
+}
@@ -1,7 +1,11 @@
 
+use serde::{Serialize, Deserialize};
+use crate::DescField;
+
 #[derive(Debug)]
 #[derive(PartialEq)]
 #[derive(Clone)]
+#[derive(Serialize, Deserialize)]
 pub struct Descriptor {
     pub desc_id: String, 
     pub point: String, 
@@ -27,6 +31,15 @@ impl Descriptor {
     pub fn set_description(&mut self, description: &str) {
         self.description = description.to_string();
     }
+
+    pub fn get_field(&self, field: DescField) -> &str {
+        match field {
+            DescField::Point => &self.point,
+            DescField::Name => &self.name,
+            DescField::Label => &self.label,
+            DescField::Description => &self.description,
+        }
+    }
 }
 
 impl Descriptor {
@@ -77,7 +90,7 @@ impl From<String> for Descriptor {
             Descriptor::new(id, name, label, description)
  
         } else {
-            let mut lines = string.lines();
+            let mut lines = string.splitn(4, '\n');
             let id = lines.next().unwrap();
             let name = lines.next().unwrap_or("");
             let label = lines.next().unwrap_or("");
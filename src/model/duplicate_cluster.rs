@@ -0,0 +1,11 @@
+use crate::Descriptor;
+
+///
+/// Descriptors with highly similar names and descriptions, candidates for merging.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCluster {
+    pub descs: Vec<Descriptor>,
+    /// Highest Jaccard similarity between the shingles of two Descriptors of the cluster.
+    pub similarity: f64,
+}
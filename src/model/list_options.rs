@@ -0,0 +1,33 @@
+use serde::Serialize;
+use crate::{DescField, DescQuery, Descriptor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+///
+/// Selects a page of a listing. Without sort_by Descriptors are listed in the order they were
+/// indexed; the filter uses the DescQuery syntax.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ListOptions {
+    pub sort_by: Option<DescField>,
+    pub order: SortOrder,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    pub filter: Option<DescQuery>,
+}
+
+///
+/// A listed Descriptor with its row number. Row numbers are positions in the unsorted,
+/// unfiltered listing, so a row keeps its number across pages, sort orders and filters.
+///
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListRow {
+    pub row: usize,
+    #[serde(flatten)]
+    pub desc: Descriptor,
+}
@@ -1,4 +1,18 @@
 pub mod descriptor;
-pub mod space;
-pub mod app;
-
+pub mod space_id;
+pub mod app_name;
+pub mod desc_error;
+pub mod point_policy;
+pub mod space_info;
+pub mod space_descriptor;
+pub mod space_diff;
+pub mod space_merge;
+pub mod search_hit;
+pub mod desc_field;
+pub mod suggestion;
+pub mod completion;
+pub mod space_stats;
+pub mod list_options;
+pub mod regex_hit;
+pub mod duplicate_cluster;
+pub mod point_matching;
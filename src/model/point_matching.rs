@@ -0,0 +1,28 @@
+use serde::{Serialize, Deserialize};
+use crate::misc::text_tools;
+
+///
+/// Decides which points of a space are the same point. Points are stored as written; the
+/// normalization only applies when points are compared.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PointMatching {
+    /// Points match byte for byte.
+    #[default]
+    Exact,
+    /// Points match regardless of case, "Café" is "café".
+    CaseFold,
+    /// Points match regardless of case and diacritics, "Café" is "cafe".
+    UnicodeFold,
+}
+
+impl PointMatching {
+
+    pub fn normalize(&self, point: &str) -> String {
+        match self {
+            PointMatching::Exact => point.to_string(),
+            PointMatching::CaseFold => point.to_lowercase(),
+            PointMatching::UnicodeFold => text_tools::fold(point),
+        }
+    }
+}
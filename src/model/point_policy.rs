@@ -0,0 +1,20 @@
+use serde::{Serialize, Deserialize};
+
+///
+/// Decides what happens when several descriptors are stored for the same point in a space.
+///
+/// All policies but Reject keep every descriptor on disk. They differ in which descriptor is
+/// preferred by lookups like get_desc and whether listings show the competing ones.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PointPolicy {
+    /// The most recently indexed descriptor is preferred and listings only show that one.
+    LatestWins,
+    /// The first indexed descriptor is preferred and listings only show that one.
+    FirstWins,
+    /// The first indexed descriptor is preferred but listings show all of them.
+    #[default]
+    KeepAll,
+    /// Adding a descriptor for an already described point fails.
+    Reject,
+}
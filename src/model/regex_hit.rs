@@ -0,0 +1,19 @@
+use crate::{DescField, Descriptor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegexOptions {
+    pub case_insensitive: bool,
+    /// Lets ^ and $ match at the start and end of every line instead of the whole field.
+    pub multiline: bool,
+}
+
+///
+/// A Descriptor whose field matched a regular expression, with the byte ranges of every match
+/// within that field.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegexHit {
+    pub desc: Descriptor,
+    pub field: DescField,
+    pub matches: Vec<(usize, usize)>,
+}
@@ -0,0 +1,12 @@
+use crate::Descriptor;
+
+///
+/// A Descriptor found by a full-text search, with its BM25 score and a snippet of the text
+/// around the first match. Matched words in the snippet are wrapped in square brackets.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub desc: Descriptor,
+    pub score: f64,
+    pub snippet: String,
+}
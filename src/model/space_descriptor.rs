@@ -0,0 +1,24 @@
+use crate::{Descriptor, SpaceId};
+
+///
+/// A Descriptor together with the space it was found in.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaceDescriptor {
+    pub space_id: SpaceId,
+    pub desc: Descriptor,
+}
+
+///
+/// Decides which results are kept when the same point is found in several spaces.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpacePrecedence {
+    /// Only the result from the earliest space in the list is kept.
+    #[default]
+    FirstSpaceWins,
+    /// Only the result from the latest space in the list is kept.
+    LastSpaceWins,
+    /// Results from all spaces are kept.
+    All,
+}
@@ -0,0 +1,31 @@
+use crate::Descriptor;
+
+///
+/// Differences between two spaces, or two snapshots of Descriptors, keyed by point.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpaceDiff {
+    /// Descriptors for points that are only described in A.
+    pub only_in_a: Vec<Descriptor>,
+    /// Descriptors for points that are only described in B.
+    pub only_in_b: Vec<Descriptor>,
+    /// Points described on both sides by different Descriptors.
+    pub changed: Vec<DescChange>,
+}
+
+impl SpaceDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.changed.is_empty()
+    }
+}
+
+///
+/// A point whose Descriptor differs between A and B, with a unified diff of the changed fields.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct DescChange {
+    pub point: String,
+    pub a: Descriptor,
+    pub b: Descriptor,
+    pub diff: String,
+}
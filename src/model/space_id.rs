@@ -0,0 +1,125 @@
+use serde::{Serialize, Deserialize};
+use std::fmt;
+use std::str::FromStr;
+use crate::DescError;
+
+const MAX_NAME_LEN: usize = 64;
+
+///
+/// Names that can not be used as a folder name on every Operative System.
+///
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+///
+/// Identifier of a space. A space id is used as a folder name, so it is validated to stay
+/// inside the spaces folder of the app.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SpaceId(String);
+
+impl SpaceId {
+    ///
+    /// Validates the id and wraps it. Ids may hold letters, digits, '-', '_' and '.', must not
+    /// start with '.', be longer than 64 characters or be a reserved file name.
+    ///
+    pub fn new(id: impl Into<String>) -> Result<Self, DescError> {
+        let id = id.into();
+        validate_folder_name(&id)?;
+        Ok(SpaceId(id))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for SpaceId {
+    fn default() -> Self {
+        SpaceId("default".to_string())
+    }
+}
+
+impl TryFrom<String> for SpaceId {
+    type Error = DescError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        SpaceId::new(s)
+    }
+}
+
+impl TryFrom<&str> for SpaceId {
+    type Error = DescError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        SpaceId::new(s)
+    }
+}
+
+impl FromStr for SpaceId {
+    type Err = DescError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SpaceId::new(s)
+    }
+}
+
+impl From<SpaceId> for String {
+    fn from(id: SpaceId) -> String {
+        id.0
+    }
+}
+
+impl AsRef<str> for SpaceId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SpaceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+///
+/// Checks that a name is safe to use as a single folder name.
+///
+pub(crate) fn validate_folder_name(name: &str) -> Result<(), DescError> {
+    let invalid = |reason: &str| Err(DescError::InvalidName {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    });
+    if name.is_empty() {
+        return invalid("it is empty");
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return invalid("it is longer than 64 characters");
+    }
+    if name.starts_with('.') {
+        return invalid("it starts with '.'");
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.') {
+        return invalid("only letters, digits, '-', '_' and '.' are allowed");
+    }
+    let stem = name.split('.').next().unwrap_or(name);
+    if RESERVED_NAMES.iter().any(|x| x.eq_ignore_ascii_case(stem)) {
+        return invalid("it is a reserved file name");
+    }
+    Ok(())
+}
+
+#[test]
+fn space_id_validation_test() {
+    assert!(SpaceId::new("team-glossary_2.0").is_ok());
+    assert!(SpaceId::new("").is_err());
+    assert!(SpaceId::new("..").is_err());
+    assert!(SpaceId::new("../../etc").is_err());
+    assert!(SpaceId::new("a/b").is_err());
+    assert!(SpaceId::new("con.txt").is_err());
+    assert!(SpaceId::new("x".repeat(65)).is_err());
+}
@@ -0,0 +1,65 @@
+use serde::{Serialize, Deserialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::{PointMatching, PointPolicy, SpaceId};
+
+///
+/// Metadata and settings of a space, stored in a file in the space folder.
+///
+/// Missing fields are read with their default values, so older files stay readable.
+///
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpaceInfo {
+    /// Name to show for the space. Empty means the space id is shown.
+    pub display_name: String,
+    pub description: String,
+    /// Seconds since the Unix epoch when the space was created.
+    pub created: u64,
+    pub owner: String,
+    /// Label given to new Descriptors created without one.
+    pub default_label: String,
+    /// What happens when several Descriptors describe the same point.
+    pub point_policy: PointPolicy,
+    /// How points are normalized before they are compared.
+    pub point_matching: PointMatching,
+    /// Read-only spaces can not be written to.
+    pub read_only: bool,
+    /// Space to fall back to when a point is not described in this space.
+    pub parent: Option<SpaceId>,
+}
+
+impl SpaceInfo {
+    pub fn new(description: &str) -> Self {
+        SpaceInfo {
+            description: description.to_string(),
+            created: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Returns the display name, or the space id if no display name is set.
+    ///
+    pub fn get_display_name(&self, space_id: &SpaceId) -> String {
+        if self.display_name.is_empty() {
+            return space_id.to_string();
+        }
+        self.display_name.clone()
+    }
+}
+
+///
+/// Counts reported for a space when spaces are listed.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpaceSummary {
+    pub space_id: SpaceId,
+    pub info: SpaceInfo,
+    /// Number of distinct Descriptors indexed in the space.
+    pub desc_count: usize,
+    /// Number of distinct points described in the space, compared by its point matching.
+    pub point_count: usize,
+}
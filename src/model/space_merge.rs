@@ -0,0 +1,63 @@
+use crate::Descriptor;
+
+///
+/// How a conflicting point was, or is to be, resolved when merging a source space into a target
+/// space. Ours is the target, theirs is the source.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeResolution {
+    /// Keep the Descriptors of the target and ignore the source.
+    Ours,
+    /// Replace the Descriptors of the target with the ones of the source.
+    Theirs,
+    /// Add the Descriptors of the source next to the ones of the target.
+    KeepBoth,
+}
+
+///
+/// Decides how points described differently in the source and the target are resolved.
+///
+pub enum MergeStrategy<'a> {
+    Ours,
+    Theirs,
+    KeepBoth,
+    /// Called with our and their Descriptor for every conflicting point.
+    Interactive(&'a mut dyn FnMut(&Descriptor, &Descriptor) -> MergeResolution),
+}
+
+impl MergeStrategy<'_> {
+    pub fn resolve(&mut self, ours: &Descriptor, theirs: &Descriptor) -> MergeResolution {
+        match self {
+            MergeStrategy::Ours => MergeResolution::Ours,
+            MergeStrategy::Theirs => MergeResolution::Theirs,
+            MergeStrategy::KeepBoth => MergeResolution::KeepBoth,
+            MergeStrategy::Interactive(callback) => callback(ours, theirs),
+        }
+    }
+}
+
+///
+/// A point described differently in source and target, and how it was resolved.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub point: String,
+    pub ours: Descriptor,
+    pub theirs: Descriptor,
+    pub resolution: MergeResolution,
+}
+
+///
+/// What a merge of one space into another did.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MergeReport {
+    /// Descriptors for points that were only described in the source and got added.
+    pub added: Vec<Descriptor>,
+    /// Number of points described the same way in both spaces.
+    pub unchanged: usize,
+    pub conflicts: Vec<MergeConflict>,
+    /// Source Descriptors the target refused because it uses PointPolicy::Reject and the point
+    /// was already described there.
+    pub rejected: Vec<Descriptor>,
+}
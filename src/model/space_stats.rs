@@ -0,0 +1,17 @@
+///
+/// Statistics over every Descriptor stored in a space, including the ones the point policy
+/// hides from listings.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpaceStats {
+    /// Number of distinct Descriptors indexed in the space.
+    pub desc_count: usize,
+    /// Number of distinct points described in the space, compared by its point matching.
+    pub distinct_points: usize,
+    /// Every label with the number of Descriptors using it, most used first.
+    pub labels: Vec<(String, usize)>,
+    /// Size of the stored Descriptors in bytes.
+    pub total_bytes: u64,
+    /// Number of points described by more than one Descriptor.
+    pub duplicates: usize,
+}
@@ -0,0 +1,10 @@
+///
+/// An existing point close to a point that was looked up, for "did you mean" prompts.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub point: String,
+    pub name: String,
+    /// Edit distance between the folded lookup and the folded point or name, whichever is closer.
+    pub distance: usize,
+}
@@ -1,42 +1,95 @@
 
 use std::collections::HashMap;
 use crate::logic::desc_director::DescDirector;
-use crate::model::app::App;
-use crate::model::space::Space;
-use crate::{descriptor_facade::DescriptorFacade, descriptor_store_fs::DescriptorStoreFS, Descriptor};
+use crate::space_guard::SpaceGuard;
+use crate::{descriptor_facade::DescriptorFacade, descriptor_store_fs::DescriptorStoreFS, AppName, Completion, DescFormatter, ListOptions, ListRow, RegexHit, RegexOptions, DescField, DuplicateCluster, Descriptor, DescError, DescQuery, MergeReport, MergeStrategy, PointMatching, PointPolicy, SearchHit, SpaceDescriptor, SpaceDiff, SpaceId, SpaceInfo, SpacePrecedence, SpaceStats, SpaceSummary, Suggestion};
 
 
 #[derive(Clone)]
 pub struct DescServiceFS {
     pub descs: DescDirector<DescriptorStoreFS>,
-    pub org_space: Space,
-    pub tmp_space: Option<Space>,
-    pub app_name: App,
+    pub org_space: SpaceId,
+    pub app_name: AppName,
 }
 
 impl DescServiceFS {
-    pub fn new(app_name: App, space_id: Space, config: String) -> Self {
+    ///
+    /// Opens the service. Fails with DescError::Config when the configuration file can not be
+    /// read.
+    ///
+    pub fn new(app_name: AppName, space_id: SpaceId, config: String) -> Result<Self, DescError> {
     
-        let descriptors = DescriptorStoreFS::new(app_name.clone(), space_id.clone(), config);
+        let descriptors = DescriptorStoreFS::new(app_name.clone(), space_id.clone(), config)?;
         let desc_facade = DescriptorFacade::new(descriptors);
 
-        DescServiceFS { 
+        Ok(DescServiceFS { 
             descs: DescDirector::new(desc_facade),
             org_space: space_id.clone(),
-            tmp_space: Option::None,
             app_name, 
-        }    
+        })
     }
 
-    pub fn set_tmp_space_id(&mut self, space_id: String) {
-        self.tmp_space = Some(Space::from(space_id));
+    ///
+    /// Opens the service without write access. Nothing is created on the file system and all
+    /// writes fail with DescError::ReadOnly.
+    ///
+    pub fn new_read_only(app_name: AppName, space_id: SpaceId, config: String) -> Result<Self, DescError> {
+
+        let descriptors = DescriptorStoreFS::new_read_only(app_name.clone(), space_id.clone(), config)?;
+
+        Ok(DescServiceFS {
+            descs: DescDirector::new(DescriptorFacade::new(descriptors)),
+            org_space: space_id,
+            app_name,
+        })
+    }
+
+    ///
+    /// Switches the underlying store to a temporary space. All following reads and writes go to
+    /// that space until revert_space_id is called. Prefer with_space or enter_space, which switch
+    /// back on their own.
+    ///
+    pub fn set_tmp_space_id(&mut self, space_id: SpaceId) {
+        self.descs.set_tmp_space_id(space_id);
     }
 
     pub fn revert_space_id(&mut self) {
-        self.tmp_space = Some(self.org_space.clone());
+        self.descs.revert_space_id();
     }
 
-    pub fn create_desc(&self, point: String, name: String, label: String, description: String) -> Descriptor {
+    ///
+    /// Returns the space currently used by the underlying store.
+    ///
+    pub fn current_space(&self) -> SpaceId {
+        self.descs.get_space_id()
+    }
+
+    pub fn get_tmp_space_id(&self) -> Option<SpaceId> {
+        self.descs.get_tmp_space_id()
+    }
+
+    ///
+    /// Switches the service to a temporary space until the returned guard is dropped.
+    ///
+    pub fn enter_space(&mut self, space_id: SpaceId) -> SpaceGuard<'_, Self> {
+        let previous = self.get_tmp_space_id();
+        self.set_tmp_space_id(space_id);
+        SpaceGuard::with_restore(self, previous, |target, previous| match previous {
+            Some(space_id) => target.set_tmp_space_id(space_id),
+            None => target.revert_space_id(),
+        })
+    }
+
+    ///
+    /// Runs the closure with the service switched to a temporary space. The space used before
+    /// is restored afterwards, also when the closure panics.
+    ///
+    pub fn with_space<R>(&mut self, space_id: SpaceId, f: impl FnOnce(&mut Self) -> R) -> R {
+        let mut guard = self.enter_space(space_id);
+        f(&mut guard)
+    }
+
+    pub fn create_desc(&self, point: String, name: String, label: String, description: String) -> Result<Descriptor, DescError> {
         self.descs.create_desc( point, name, label, description)
     }
 
@@ -44,11 +97,186 @@ impl DescServiceFS {
         self.descs.ls_descriptor_notes()
     }
 
-    pub fn get_desc_ls_line_number(&self, line_number: String) -> String {
+    pub fn ls_descs_formatted(&self, options: &ListOptions, formatter: &dyn DescFormatter) -> String {
+        self.descs.ls_formatted(options, formatter)
+    }
+
+    pub fn list(&self, options: &ListOptions) -> Vec<ListRow> {
+        self.descs.list(options)
+    }
+
+    pub fn get_desc_ls_line_number(&self, line_number: String) -> Result<String, DescError> {
         self.descs.get_desc_ls_line_number(line_number)
     }
 
-    pub fn get_descs_hashmap_for_list(&self, list: Vec<String>) -> HashMap<String, Descriptor> {
+    pub fn get_desc_ids_for_rows(&self, rows: &str) -> Result<Vec<String>, DescError> {
+        self.descs.get_desc_ids_for_rows(rows)
+    }
+
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        self.descs.search(query)
+    }
+
+    pub fn query(&self, query: &str) -> Result<Vec<Descriptor>, DescError> {
+        self.descs.query(query)
+    }
+
+    pub fn suggest(&self, point: &str, max_distance: usize) -> Vec<Suggestion> {
+        self.descs.suggest(point, max_distance)
+    }
+
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<Completion> {
+        self.descs.complete(prefix, limit)
+    }
+
+    pub fn label_counts(&self) -> Vec<(String, usize)> {
+        self.descs.label_counts()
+    }
+
+    pub fn get_descs_by_label(&self, label: &str) -> Vec<Descriptor> {
+        self.descs.get_descs_by_label(label)
+    }
+
+    pub fn space_stats(&self) -> SpaceStats {
+        self.descs.get_space_stats()
+    }
+
+    pub fn iter_descs(&self) -> impl Iterator<Item = Descriptor> + '_ {
+        self.descs.iter_descs()
+    }
+
+    pub fn iter_descs_by_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = Descriptor> + 'a {
+        self.descs.iter_descs_by_label(label)
+    }
+
+    pub fn iter_containing(&self, query: &str) -> impl Iterator<Item = Descriptor> + '_ {
+        self.descs.iter_containing(query)
+    }
+
+    pub fn iter_query<'a>(&'a self, query: &'a DescQuery) -> impl Iterator<Item = Descriptor> + 'a {
+        self.descs.iter_query(query)
+    }
+
+    pub fn search_regex(&self, field: DescField, pattern: &str, options: &RegexOptions) -> Result<Vec<RegexHit>, DescError> {
+        self.descs.search_regex(field, pattern, options)
+    }
+
+    pub fn find_near_duplicates(&self, threshold: f64) -> Vec<DuplicateCluster> {
+        self.descs.find_near_duplicates(threshold)
+    }
+
+    pub fn get_descs_hashmap_for_list(&self, list: Vec<String>) -> HashMap<String, Vec<Descriptor>> {
         self.descs.get_descs_hashmap_for_list(list)
     }
+
+    pub fn get_descs_for_point(&self, point: &str) -> Vec<Descriptor> {
+        self.descs.get_descs_for_point(point)
+    }
+
+    pub fn get_preferred_desc(&self, point: &str) -> Option<Descriptor> {
+        self.descs.get_preferred_desc(point)
+    }
+
+    pub fn get_point_policy(&self) -> PointPolicy {
+        self.descs.get_point_policy()
+    }
+
+    pub fn set_point_policy(&self, policy: PointPolicy) -> Result<(), DescError> {
+        self.descs.set_point_policy(policy)
+    }
+
+    pub fn get_point_matching(&self) -> PointMatching {
+        self.descs.get_point_matching()
+    }
+
+    pub fn set_point_matching(&self, matching: PointMatching) -> Result<(), DescError> {
+        self.descs.set_point_matching(matching)
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.descs.is_read_only()
+    }
+
+    pub fn find_point_in_spaces(&self, point: &str, spaces: &[SpaceId], precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descs.find_point_in_spaces(point, spaces, precedence)
+    }
+
+    pub fn find_point_anywhere(&self, point: &str, precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descs.find_point_anywhere(point, precedence)
+    }
+
+    pub fn search_in_spaces(&self, query: &str, spaces: &[SpaceId], precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descs.search_in_spaces(query, spaces, precedence)
+    }
+
+    pub fn search_anywhere(&self, query: &str, precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.descs.search_anywhere(query, precedence)
+    }
+
+    pub fn copy_desc(&self, desc_id: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Descriptor, DescError> {
+        self.descs.copy_desc(desc_id, from_space, to_space)
+    }
+
+    pub fn move_desc(&self, desc_id: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Descriptor, DescError> {
+        self.descs.move_desc(desc_id, from_space, to_space)
+    }
+
+    pub fn copy_descs(&self, query: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Vec<Descriptor>, DescError> {
+        self.descs.copy_descs(query, from_space, to_space)
+    }
+
+    pub fn move_descs(&self, query: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Vec<Descriptor>, DescError> {
+        self.descs.move_descs(query, from_space, to_space)
+    }
+
+    pub fn get_inherited_desc(&self, point: &str) -> Option<SpaceDescriptor> {
+        self.descs.get_inherited_desc(point)
+    }
+
+    pub fn get_space_chain(&self) -> Vec<SpaceId> {
+        self.descs.get_space_chain()
+    }
+
+    pub fn set_space_parent(&self, space_id: &SpaceId, parent: Option<SpaceId>) -> Result<(), DescError> {
+        self.descs.set_space_parent(space_id, parent)
+    }
+
+    pub fn diff_spaces(&self, space_a: &SpaceId, space_b: &SpaceId) -> Result<SpaceDiff, DescError> {
+        self.descs.diff_spaces(space_a, space_b)
+    }
+
+    pub fn merge_space(&self, source_space: &SpaceId, target_space: &SpaceId, strategy: MergeStrategy) -> Result<MergeReport, DescError> {
+        self.descs.merge_space(source_space, target_space, strategy)
+    }
+
+    pub fn ls_spaces(&self) -> Vec<SpaceSummary> {
+        self.descs.ls_spaces()
+    }
+
+    pub fn get_space_info(&self, space_id: &SpaceId) -> Option<SpaceInfo> {
+        self.descs.get_space_info(space_id)
+    }
+
+    ///
+    /// Returns the metadata and settings of the space currently in use.
+    ///
+    pub fn space_info(&self) -> SpaceInfo {
+        self.descs.get_current_space_info()
+    }
+
+    pub fn set_space_info(&self, space_id: &SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.descs.set_space_info(space_id, info)
+    }
+
+    pub fn create_space(&self, space_id: SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.descs.create_space(space_id, info)
+    }
+
+    pub fn rename_space(&self, space_id: SpaceId, new_space_id: SpaceId) -> Result<(), DescError> {
+        self.descs.rename_space(space_id, new_space_id)
+    }
+
+    pub fn delete_space(&self, space_id: SpaceId, confirmation: String) -> Result<(), DescError> {
+        self.descs.delete_space(space_id, confirmation)
+    }
 }
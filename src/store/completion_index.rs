@@ -0,0 +1,65 @@
+use std::collections::{BTreeMap, HashSet};
+use crate::Completion;
+use crate::misc::text_tools;
+
+///
+/// Sorted prefix index over the points and names of a space. Keys are folded, so prefixes match
+/// regardless of case and diacritics.
+///
+pub struct CompletionIndex {
+    entries: Vec<Completion>,
+    points: BTreeMap<String, Vec<usize>>,
+    names: BTreeMap<String, Vec<usize>>,
+}
+
+impl CompletionIndex {
+
+    pub fn new(entries: Vec<Completion>) -> Self {
+        let mut points: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut names: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (pos, entry) in entries.iter().enumerate() {
+            points.entry(text_tools::fold(&entry.point)).or_default().push(pos);
+            if !entry.name.is_empty() {
+                names.entry(text_tools::fold(&entry.name)).or_default().push(pos);
+            }
+        }
+        CompletionIndex { entries, points, names }
+    }
+
+    ///
+    /// Returns up to limit entries whose point starts with the prefix, in point order, followed by
+    /// entries whose name starts with it, in name order.
+    ///
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<Completion> {
+        let prefix = text_tools::fold(prefix);
+        let mut seen: HashSet<usize> = HashSet::new();
+        [&self.points, &self.names]
+            .into_iter()
+            .flat_map(|map| {
+                map.range(prefix.clone()..)
+                    .take_while(|(key, _)| key.starts_with(&prefix))
+                    .flat_map(|(_, positions)| positions.iter().copied())
+            })
+            .filter(|pos| seen.insert(*pos))
+            .take(limit)
+            .map(|pos| self.entries[pos].clone())
+            .collect()
+    }
+}
+
+#[test]
+fn completion_index_test() {
+    let entry = |point: &str, name: &str| Completion { point: point.to_string(), name: name.to_string(), label: "l".to_string() };
+    let index = CompletionIndex::new(vec![
+        entry("people/bob", "Bob"),
+        entry("people/ada", "Ada Lovelace"),
+        entry("places/paris", "Paris"),
+        entry("x/1", "People's Front"),
+    ]);
+    let points = |prefix, limit| index.complete(prefix, limit).into_iter().map(|x| x.point).collect::<Vec<String>>();
+
+    assert_eq!(points("Peo", 10), vec!["people/ada", "people/bob", "x/1"]);
+    assert_eq!(points("p", 2), vec!["people/ada", "people/bob"]);
+    assert_eq!(points("par", 10), vec!["places/paris"]);
+    assert!(points("z", 10).is_empty());
+}
@@ -1,8 +1,14 @@
-use crate::Descriptor;
+use crate::{Completion, Descriptor, DescError, DescField, DescQuery, ListOptions, ListRow, RegexHit, RegexOptions, SearchHit, SortOrder, MergeConflict, MergeReport, MergeResolution, MergeStrategy, PointMatching, PointPolicy, SpaceDescriptor, SpaceDiff, SpaceId, SpaceInfo, SpacePrecedence, SpaceStats, SpaceSummary, Suggestion};
 use delve::{EnumFromStr, EnumToStr};
-use std::collections::HashMap;
+use regex::RegexBuilder;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use super::completion_index::CompletionIndex;
 use super::descriptor_store::DescriptorStore;
-use crate::misc::descriptor_tools;
+use super::space_guard::SpaceGuard;
+use crate::misc::{descriptor_tools, diff_tools, text_tools};
+use crate::misc::search_tools::SearchIndex;
 
 
 #[derive(EnumFromStr, EnumToStr, Clone)]
@@ -25,79 +31,1190 @@ impl ToString for DescIndex {
 }
 
 
+/// Fewest Descriptors worth loading on a thread of their own.
+const PARALLEL_LOAD_MIN_CHUNK: usize = 32;
+
+///
+/// The last prefix index built for completion, with the index fingerprint it was built for.
+///
+type CompletionCache = Arc<Mutex<Option<(u64, Arc<CompletionIndex>)>>>;
+
+///
+/// The last full-text index built for search, with the index fingerprint it was built for.
+///
+type SearchCache = Arc<Mutex<Option<(u64, Arc<SearchIndex>)>>>;
+
 #[derive(Clone)]
 pub struct DescriptorFacade<T:DescriptorStore> {
     storage: T, 
+    completions: CompletionCache,
+    searches: SearchCache,
 }
 
 impl<T:DescriptorStore> DescriptorFacade<T> {
 
     pub fn new(storage: T) -> Self{
-        DescriptorFacade {storage}
+        DescriptorFacade {storage, completions: Arc::new(Mutex::new(None)), searches: Arc::new(Mutex::new(None))}
     }
 
     ///
     /// This method stores a Descriptor after creating its desc_id.
     /// Before returning the Descriptor, indexes are creates also.
     ///
-    pub fn add_desc_n_index(&self, desc: Descriptor) -> Descriptor {
-        let desc_id = self.add_desc(desc.clone());
+    /// Adding a Descriptor that is already indexed for its point is a no-op. If the point is
+    /// described by another Descriptor and the space uses PointPolicy::Reject an error is returned.
+    /// Read-only stores and spaces always return DescError::ReadOnly.
+    ///
+    pub fn add_desc_n_index(&self, desc: Descriptor) -> Result<Descriptor, DescError> {
+        if self.is_read_only() {
+            return Err(DescError::ReadOnly(self.get_space_id().to_string()));
+        }
         let mut result = desc.clone();
-        result.desc_id = desc_id;
-        self.add_desc_index(result.clone());
-        result
+        result.desc_id = descriptor_tools::get_desc_id(&desc);
+
+        let existing = self.get_descs_for_point(&desc.point);
+        if existing.iter().any(|x| x.desc_id == result.desc_id) {
+            return Ok(result);
+        }
+        if let (PointPolicy::Reject, Some(other)) = (self.get_point_policy(), existing.first()) {
+            return Err(DescError::PointAlreadyDescribed {
+                point: other.point.clone(),
+                desc_id: other.desc_id.clone(),
+            });
+        }
+
+        self.add_desc(desc)?;
+        self.add_desc_index(result.clone())?;
+        Ok(result)
     }
 
     ///
     /// Stores a Descriptor after creating its desc_id.
     /// Consider using add_desc_n_index instead as it creates indexes also.
     ///
-    pub fn add_desc(&self, desc: Descriptor) -> String {
+    pub fn add_desc(&self, desc: Descriptor) -> Result<String, DescError> {
         let id = descriptor_tools::get_desc_id(&desc);
-        self.storage.add_desc(desc, id.clone());
-        id
+        self.storage.add_desc(desc, id.clone())?;
+        Ok(id)
     }
 
     ///        
     /// Helper method that adds indexes to a Descriptor.
     /// Consider using add_desc_n_index as it calls this method and stores the Descriptor as well.
     ///
-    pub fn add_desc_index(&self, desc: Descriptor) {
-        self.storage.index_desc(desc);
+    pub fn add_desc_index(&self, desc: Descriptor) -> Result<(), DescError> {
+        self.storage.index_desc(desc)
     }
 
     pub fn get_descs(&self, points: Vec<&str>) -> Vec<Descriptor> {
         self.storage.get_descs(points)
     }
 
+    ///
+    /// Returns the preferred Descriptor for each point, or a Descriptor holding nothing but the
+    /// point when it is not described.
+    ///
     pub fn get_descs_or_else_ids(&self, points: Vec<String>) -> Vec<Descriptor> {
     
-        self.storage.get_descs_or_else_ids(points)
+        points.iter().map(|x| self.get_desc_or_id(x)).collect()
+    }
+
+    ///
+    /// Returns the preferred Descriptor for the point from this space or, if it is not described
+    /// here, from the first parent space describing it.
+    ///
+    pub fn get_desc_or_id(&self, point: &str) -> Descriptor {
+        self.get_inherited_desc(point).map(|x| x.desc).unwrap_or_else(|| Descriptor {
+            point: point.to_string(),
+            ..Default::default()
+        })
     }
 
-    pub fn get_descs_hashmap_for_list(&self, list: Vec<String>) -> HashMap<String, Descriptor> {
-        let mut descs: HashMap<String, Descriptor> = HashMap::new();
-        self.get_descs_or_else_ids(list)
+    ///
+    /// Resolves the point through the inheritance chain of the space. The result is tagged with
+    /// the space that described the point.
+    ///
+    pub fn get_inherited_desc(&self, point: &str) -> Option<SpaceDescriptor> {
+        self.get_space_chain()
+            .into_iter()
+            .find_map(|space_id| {
+                self.view_space(&space_id)
+                    .get_preferred_desc(point)
+                    .map(|desc| SpaceDescriptor { space_id, desc })
+            })
+    }
+
+    ///
+    /// Returns the space in use followed by its parent, the parent of that and so on.
+    ///
+    pub fn get_space_chain(&self) -> Vec<SpaceId> {
+        self.get_space_chain_from(&self.get_space_id())
+    }
+
+    fn get_space_chain_from(&self, space_id: &SpaceId) -> Vec<SpaceId> {
+        let mut chain: Vec<SpaceId> = vec![space_id.clone()];
+        let mut current = space_id.clone();
+        while let Some(parent) = self.get_space_info(&current).and_then(|x| x.parent) {
+            if chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent.clone());
+            current = parent;
+        }
+        chain
+    }
+
+    ///
+    /// Makes a space inherit from a parent space, or stop inheriting with None. Fails if either
+    /// space does not exist or the space would end up inheriting from itself.
+    ///
+    pub fn set_space_parent(&self, space_id: &SpaceId, parent: Option<SpaceId>) -> Result<(), DescError> {
+        let mut info = self.get_space_info(space_id)
+            .ok_or(DescError::SpaceNotFound(space_id.to_string()))?;
+        if let Some(parent_id) = &parent {
+            if self.get_space_info(parent_id).is_none() {
+                return Err(DescError::SpaceNotFound(parent_id.to_string()));
+            }
+            if self.get_space_chain_from(parent_id).iter().any(|x| x == space_id) {
+                return Err(DescError::InheritanceCycle(space_id.to_string()));
+            }
+        }
+        info.parent = parent;
+        self.storage.set_space_info(space_id, info)
+    }
+
+    ///
+    /// Looks up many points at once. Every requested point maps to all its visible Descriptors,
+    /// so Descriptors sharing a point do not overwrite each other. Points not described in this
+    /// space map to the preferred Descriptor of the first parent space describing them, or to a
+    /// Descriptor holding nothing but the point.
+    ///
+    /// The point index of this space and of every parent needed is read once, and the Descriptor
+    /// files are loaded in parallel. Repeated points are looked up once.
+    ///
+    pub fn get_descs_hashmap_for_list(&self, list: Vec<String>) -> HashMap<String, Vec<Descriptor>> where T: Sync {
+        let matching = self.get_point_matching();
+        let mut wanted: HashMap<String, HashSet<&str>> = HashMap::new();
+        list.iter().for_each(|x| {
+            wanted.entry(matching.normalize(x)).or_default().insert(x.as_str());
+        });
+
+        let mut ids_by_point: HashMap<&str, Vec<String>> = HashMap::new();
+        for (point, id) in self.iter_visible_index_entries() {
+            for requested in wanted.get(&matching.normalize(&point)).into_iter().flatten() {
+                ids_by_point.entry(*requested).or_default().push(id.clone());
+            }
+        }
+        let ids: HashSet<&str> = ids_by_point.values().flatten().map(String::as_str).collect();
+        let loaded = self.load_descs_parallel(ids.into_iter().collect());
+
+        let mut result: HashMap<String, Vec<Descriptor>> = HashMap::new();
+        for (point, ids) in ids_by_point {
+            let descs: Vec<Descriptor> = ids.iter().filter_map(|id| loaded.get(id).cloned()).collect();
+            if !descs.is_empty() {
+                result.insert(point.to_string(), descs);
+            }
+        }
+
+        let missing: Vec<&str> = wanted
+            .values()
+            .flatten()
+            .copied()
+            .filter(|x| !result.contains_key(*x))
+            .collect();
+        let mut inherited = self.get_inherited_descs(missing);
+        for point in list.iter() {
+            if !result.contains_key(point) {
+                let desc = inherited
+                    .remove(point.as_str())
+                    .unwrap_or_else(|| Descriptor { point: point.clone(), ..Default::default() });
+                result.insert(point.clone(), vec![desc]);
+            }
+        }
+        result
+    }
+
+    ///
+    /// Resolves points through the parent spaces, the way get_inherited_desc does for one point.
+    /// The visible index of every parent is read once for all points still missing, and the
+    /// Descriptors found there are loaded in parallel.
+    ///
+    fn get_inherited_descs<'a>(&self, mut missing: Vec<&'a str>) -> HashMap<&'a str, Descriptor> where T: Sync {
+        let mut inherited: HashMap<&str, Descriptor> = HashMap::new();
+        for space_id in self.get_space_chain().into_iter().skip(1) {
+            if missing.is_empty() {
+                break;
+            }
+            let parent = self.view_space(&space_id);
+            let matching = parent.get_point_matching();
+            let mut wanted: HashMap<String, Vec<&str>> = HashMap::new();
+            missing.iter().for_each(|x| wanted.entry(matching.normalize(x)).or_default().push(x));
+
+            let mut ids: HashMap<&str, String> = HashMap::new();
+            for (point, id) in parent.iter_visible_index_entries() {
+                if let Some(requested) = wanted.remove(&matching.normalize(&point)) {
+                    requested.into_iter().for_each(|x| {
+                        ids.insert(x, id.clone());
+                    });
+                    if wanted.is_empty() {
+                        break;
+                    }
+                }
+            }
+            let unique_ids: HashSet<&str> = ids.values().map(String::as_str).collect();
+            let loaded = parent.load_descs_parallel(unique_ids.into_iter().collect());
+            for (point, id) in ids.iter() {
+                if let Some(desc) = loaded.get(id) {
+                    inherited.insert(point, desc.clone());
+                }
+            }
+            missing.retain(|x| !inherited.contains_key(x));
+        }
+        inherited
+    }
+
+    ///
+    /// Loads Descriptors by desc_id, spreading the files over as many threads as the machine
+    /// runs in parallel. Small batches are loaded on one thread. A panic while loading is passed
+    /// on to the caller.
+    ///
+    fn load_descs_parallel(&self, ids: Vec<&str>) -> HashMap<String, Descriptor> where T: Sync {
+        let threads = std::thread::available_parallelism().map(|x| x.get()).unwrap_or(1);
+        let chunk_size = ids.len().div_ceil(threads).max(PARALLEL_LOAD_MIN_CHUNK);
+        let storage = &self.storage;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = ids
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk.iter()
+                            .filter_map(|id| storage.get_desc_by_id(id).map(|desc| (id.to_string(), desc)))
+                            .collect::<Vec<(String, Descriptor)>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|x| x.join().unwrap_or_else(|err| std::panic::resume_unwind(err)))
+                .collect()
+        })
+    }
+
+    ///
+    /// Returns all Descriptors of the space that are visible under its point policy.
+    ///
+    pub fn get_all_descs(&self) -> Vec<Descriptor> {
+        self.get_visible_index_entries()
             .iter()
-            .for_each(|x|{
-                descs.insert(x.point.clone(), x.clone());
+            .filter_map(|(_, id)| self.storage.get_desc_by_id(id))
+            .collect()
+    }
+
+    ///
+    /// Returns the visible Descriptors matching the query, in the same order as get_all_descs.
+    /// The query is evaluated on the stored fields, the same way iter_query does.
+    ///
+    pub fn query_descs(&self, query: &DescQuery) -> Vec<Descriptor> {
+        self.iter_query(query).collect()
+    }
+
+    ///
+    /// Returns the visible points whose point or name is within max_distance edits of the given
+    /// point, ignoring case and diacritics. Closest points come first.
+    ///
+    pub fn suggest(&self, point: &str, max_distance: usize) -> Vec<Suggestion> {
+        let wanted = text_tools::fold(point);
+        let names = self.get_index_values(DescField::Name);
+        let mut best: HashMap<String, Suggestion> = HashMap::new();
+
+        for (found_point, id) in self.get_visible_index_entries() {
+            let name = names.get(&id).cloned().unwrap_or_default();
+            let distance = text_tools::levenshtein(&wanted, &text_tools::fold(&found_point))
+                .min(text_tools::levenshtein(&wanted, &text_tools::fold(&name)));
+            if distance > max_distance || best.get(&found_point).is_some_and(|x| x.distance <= distance) {
+                continue;
+            }
+            best.insert(found_point.clone(), Suggestion { point: found_point, name, distance });
+        }
+
+        let mut suggestions: Vec<Suggestion> = best.into_values().collect();
+        suggestions.sort_by(|a, b| a.distance.cmp(&b.distance).then_with(|| a.point.cmp(&b.point)));
+        suggestions
+    }
+
+    ///
+    /// Returns up to limit visible Descriptors whose point or name starts with the prefix,
+    /// ignoring case and diacritics. The prefix index is kept between calls and only rebuilt
+    /// when the index fingerprint of the store changes.
+    ///
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<Completion> {
+        self.get_completion_index().complete(prefix, limit)
+    }
+
+    ///
+    /// Full-text search over the names, labels and descriptions of the visible Descriptors,
+    /// ranked by BM25. The search index is kept between calls and only rebuilt when the index
+    /// fingerprint of the store changes.
+    ///
+    pub fn search(&self, query: &str) -> Vec<SearchHit> {
+        self.get_search_index().search(query)
+    }
+
+    fn get_search_index(&self) -> Arc<SearchIndex> {
+        let fingerprint = self.storage.get_index_fingerprint();
+        let mut cache = self.searches.lock().unwrap_or_else(|x| x.into_inner());
+        if let Some((cached, index)) = cache.as_ref() {
+            if *cached == fingerprint {
+                return index.clone();
+            }
+        }
+
+        let index = Arc::new(SearchIndex::new(self.get_all_descs()));
+        *cache = Some((fingerprint, index.clone()));
+        index
+    }
+
+    fn get_completion_index(&self) -> Arc<CompletionIndex> {
+        let fingerprint = self.storage.get_index_fingerprint();
+        let mut cache = self.completions.lock().unwrap_or_else(|x| x.into_inner());
+        if let Some((cached, index)) = cache.as_ref() {
+            if *cached == fingerprint {
+                return index.clone();
+            }
+        }
+
+        let names = self.get_index_values(DescField::Name);
+        let labels = self.get_index_values(DescField::Label);
+        let entries: Vec<Completion> = self.get_visible_index_entries()
+            .into_iter()
+            .map(|(point, id)| Completion {
+                point,
+                name: names.get(&id).cloned().unwrap_or_default(),
+                label: labels.get(&id).cloned().unwrap_or_default(),
+            })
+            .collect();
+        let index = Arc::new(CompletionIndex::new(entries));
+        *cache = Some((fingerprint, index.clone()));
+        index
+    }
+
+    ///
+    /// Returns every label of the visible Descriptors with the number of Descriptors using it,
+    /// most used first.
+    ///
+    pub fn label_counts(&self) -> Vec<(String, usize)> {
+        let labels = self.get_index_values(DescField::Label);
+        Self::count_labels(self.get_all_desc_ids().iter().filter_map(|id| labels.get(id)).map(String::as_str))
+    }
+
+    ///
+    /// Returns the visible Descriptors with exactly the given label.
+    ///
+    pub fn get_descs_by_label(&self, label: &str) -> Vec<Descriptor> {
+        let labels = self.get_index_values(DescField::Label);
+        self.get_all_desc_ids()
+            .iter()
+            .filter(|id| labels.get(*id).is_some_and(|x| x == label))
+            .filter_map(|id| self.storage.get_desc_by_id(id))
+            .collect()
+    }
+
+    ///
+    /// Counts the Descriptors of the space. Points are compared by the point matching of the space.
+    ///
+    pub fn get_space_stats(&self) -> SpaceStats {
+        let matching = self.get_point_matching();
+        let point_indexes = self.storage.get_desc_point_indexes();
+        let mut seen: HashSet<(String, &str)> = HashSet::new();
+        let mut known_ids: HashSet<&str> = HashSet::new();
+        let mut ids: Vec<&str> = Vec::new();
+        let mut descs_per_point: HashMap<String, usize> = HashMap::new();
+        for (point, id) in point_indexes.lines().filter_map(descriptor_tools::parse_desc_index_line) {
+            let point = matching.normalize(point);
+            if !seen.insert((point.clone(), id)) {
+                continue;
+            }
+            if known_ids.insert(id) {
+                ids.push(id);
+            }
+            *descs_per_point.entry(point).or_default() += 1;
+        }
+
+        let descs: Vec<Descriptor> = ids.iter().filter_map(|id| self.storage.get_desc_by_id(id)).collect();
+        SpaceStats {
+            desc_count: ids.len(),
+            distinct_points: descs_per_point.len(),
+            labels: Self::count_labels(descs.iter().map(|x| x.label.as_str())),
+            total_bytes: descs.iter().map(|x| String::from(x.clone()).len() as u64).sum(),
+            duplicates: descs_per_point.values().filter(|x| **x > 1).count(),
+        }
+    }
+
+    fn count_labels<'a>(labels: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        labels.for_each(|x| *counts.entry(x).or_default() += 1);
+        let mut result: Vec<(String, usize)> = counts.into_iter().map(|(label, count)| (label.to_string(), count)).collect();
+        result.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        result
+    }
+
+    ///
+    /// Returns one page of the visible Descriptors. The filter is evaluated on the stored fields,
+    /// the same way query_descs does, so every Descriptor is loaded when there is one. Sorting
+    /// works on the field indexes, so without a filter only the Descriptors on the page are
+    /// loaded.
+    ///
+    pub fn list_descs(&self, options: &ListOptions) -> Vec<ListRow> {
+        let values: HashMap<DescField, HashMap<String, String>> = DescField::ALL
+            .iter()
+            .map(|field| (*field, self.get_index_values(*field)))
+            .collect();
+        let value_of = |id: &str, field: DescField| values[&field].get(id).cloned().unwrap_or_default();
+
+        let ids = self.get_all_desc_ids();
+        let mut rows: Vec<(usize, &str)> = ids.iter().map(String::as_str).enumerate().collect();
+        if let Some(filter) = &options.filter {
+            let matching = self.get_point_matching();
+            let filter = filter.normalize_points(matching);
+            rows.retain(|(_, id)| {
+                self.storage.get_desc_by_id(id).is_some_and(|x| Self::query_matches(&filter, matching, &x))
             });
-         descs   
+        }
+        match (options.sort_by, options.order) {
+            (Some(field), SortOrder::Ascending) => rows.sort_by_cached_key(|(_, id)| value_of(id, field)),
+            (Some(field), SortOrder::Descending) => rows.sort_by_cached_key(|(_, id)| Reverse(value_of(id, field))),
+            (None, SortOrder::Ascending) => {}
+            (None, SortOrder::Descending) => rows.reverse(),
+        }
+
+        rows.into_iter()
+            .skip(options.offset)
+            .take(options.limit.unwrap_or(usize::MAX))
+            .filter_map(|(row, id)| self.storage.get_desc_by_id(id).map(|desc| ListRow { row, desc }))
+            .collect()
     }
 
-    pub fn get_all_descs(&self) -> Vec<Descriptor> {
-        self.storage.get_all_descs()
+    ///
+    /// Reads the index of a field as a map from desc_id to the indexed value.
+    ///
+    fn get_index_values(&self, field: DescField) -> HashMap<String, String> {
+        let index = match field {
+            DescField::Point => self.storage.get_desc_point_indexes(),
+            DescField::Name => self.storage.get_desc_name_indexes(),
+            DescField::Label => self.storage.get_desc_label_indexes(),
+            DescField::Description => self.storage.get_desc_description_indexes(),
+        };
+        index
+            .lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .map(|(value, id)| (id.to_string(), value.to_string()))
+            .collect()
+    }
+
+    ///
+    /// Lazily loads the visible Descriptors, in the same order as get_all_descs. Descriptor
+    /// files are read one at a time as the iterator advances.
+    ///
+    pub fn iter_descs(&self) -> impl Iterator<Item = Descriptor> + '_ {
+        self.iter_visible_index_entries().filter_map(|(_, id)| self.storage.get_desc_by_id(&id))
+    }
+
+    ///
+    /// Lazily loads the visible Descriptors with exactly the given label. The label index is
+    /// read first, so only the files of matching Descriptors are opened.
+    ///
+    pub fn iter_descs_by_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = Descriptor> + 'a {
+        let ids: HashSet<String> = self.storage
+            .get_desc_label_indexes()
+            .lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .filter(|x| x.0 == label)
+            .map(|x| x.1.to_string())
+            .collect();
+        self.iter_visible_index_entries()
+            .filter(move |(_, id)| ids.contains(id))
+            .filter_map(|(_, id)| self.storage.get_desc_by_id(&id))
+    }
+
+    ///
+    /// Lazily yields the visible Descriptors containing the query in any field, ignoring case.
+    ///
+    pub fn iter_containing(&self, query: &str) -> impl Iterator<Item = Descriptor> + '_ {
+        let query = query.to_lowercase();
+        self.iter_descs().filter(move |x| Self::desc_contains(x, &query))
+    }
+
+    ///
+    /// Lazily yields the visible Descriptors matching the query. Point terms compare points the
+    /// way the space does.
+    ///
+    pub fn iter_query<'a>(&'a self, query: &'a DescQuery) -> impl Iterator<Item = Descriptor> + 'a {
+        let matching = self.get_point_matching();
+        let query = query.normalize_points(matching);
+        self.iter_descs().filter(move |x| Self::query_matches(&query, matching, x))
     }
 
+    ///
+    /// Evaluates a query, with point terms already normalized, on the fields of a Descriptor.
+    ///
+    fn query_matches(query: &DescQuery, matching: PointMatching, desc: &Descriptor) -> bool {
+        query.matches(&|field| match field {
+            DescField::Point => matching.normalize(&desc.point),
+            _ => desc.get_field(field).to_string(),
+        })
+    }
+
+    ///
+    /// Returns the visible Descriptors whose field matches the regular expression, with the
+    /// positions of all matches. Descriptors are loaded one at a time, so the description is
+    /// matched with its line breaks.
+    ///
+    pub fn search_regex(&self, field: DescField, pattern: &str, options: &RegexOptions) -> Result<Vec<RegexHit>, DescError> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(options.case_insensitive)
+            .multi_line(options.multiline)
+            .build()
+            .map_err(|x| DescError::InvalidPattern(x.to_string()))?;
+        let hits = self.iter_descs()
+            .filter_map(|desc| {
+                let matches: Vec<(usize, usize)> = regex
+                    .find_iter(desc.get_field(field))
+                    .map(|x| (x.start(), x.end()))
+                    .collect();
+                (!matches.is_empty()).then_some(RegexHit { desc, field, matches })
+            })
+            .collect();
+        Ok(hits)
+    }
+
+    ///
+    /// Returns the desc_ids of all visible Descriptors, in the same order as get_all_descs.
+    ///
     pub fn get_all_desc_ids(&self) -> Vec<String> {
-        let point_indexes = self.storage.get_desc_point_indexes();
-        let lines = point_indexes.lines(); 
-        let ids: Vec<String> = lines.map(|x|{x.split_once(' ').unwrap().1.to_string()}).collect();
-        ids
+        self.get_visible_index_entries().into_iter().map(|(_, id)| id).collect()
     }
 
+    ///
+    /// Returns the preferred Descriptor for the point, or an empty Descriptor if there is none.
+    ///
     pub fn get_desc(&self, name: &str) -> Descriptor {
-        self.storage.get_desc(name)
+        self.get_preferred_desc(name).unwrap_or_default()
+    }
+
+    ///
+    /// Returns every Descriptor stored for the point, in the order they were indexed.
+    ///
+    pub fn get_descs_for_point(&self, point: &str) -> Vec<Descriptor> {
+        self.storage.get_descs_for_point(point)
+    }
+
+    ///
+    /// Picks the Descriptor for the point that the point policy of the space prefers.
+    ///
+    pub fn get_preferred_desc(&self, point: &str) -> Option<Descriptor> {
+        let descs = self.get_descs_for_point(point);
+        match self.get_point_policy() {
+            PointPolicy::LatestWins => descs.into_iter().next_back(),
+            PointPolicy::FirstWins | PointPolicy::KeepAll | PointPolicy::Reject => descs.into_iter().next(),
+        }
+    }
+
+    pub fn get_point_policy(&self) -> PointPolicy {
+        self.storage.get_point_policy()
+    }
+
+    pub fn set_point_policy(&self, policy: PointPolicy) -> Result<(), DescError> {
+        self.storage.set_point_policy(policy)
+    }
+
+    pub fn get_point_matching(&self) -> PointMatching {
+        self.storage.get_point_matching()
+    }
+
+    ///
+    /// Changes how points are compared in the space in use. Stored points are left as they
+    /// were written.
+    ///
+    pub fn set_point_matching(&self, matching: PointMatching) -> Result<(), DescError> {
+        self.storage.set_point_matching(matching)
+    }
+
+    ///
+    /// Tells if the space in use refuses writes, because the store was opened read-only or the
+    /// space is marked read-only.
+    ///
+    pub fn is_read_only(&self) -> bool {
+        self.storage.is_read_only()
     }
+
+    ///
+    /// Switches the underlying store to a temporary space.
+    ///
+    pub fn set_tmp_space_id(&mut self, space_id: SpaceId) {
+        self.storage.set_tmp_space_id(space_id)
+    }
+
+    pub fn revert_space_id(&mut self) {
+        self.storage.revert_space_id()
+    }
+
+    ///
+    /// Returns the space the underlying store currently reads from and writes to.
+    ///
+    pub fn get_space_id(&self) -> SpaceId {
+        self.storage.get_space_id()
+    }
+
+    pub fn get_tmp_space_id(&self) -> Option<SpaceId> {
+        self.storage.get_tmp_space_id()
+    }
+
+    ///
+    /// Switches the underlying store to a temporary space until the returned guard is dropped.
+    ///
+    pub fn enter_space(&mut self, space_id: SpaceId) -> SpaceGuard<'_, Self> {
+        let previous = self.get_tmp_space_id();
+        self.set_tmp_space_id(space_id);
+        SpaceGuard::with_restore(self, previous, |target, previous| match previous {
+            Some(space_id) => target.set_tmp_space_id(space_id),
+            None => target.revert_space_id(),
+        })
+    }
+
+    ///
+    /// Runs the closure with the underlying store switched to a temporary space. The space used before
+    /// is restored afterwards, also when the closure panics.
+    ///
+    pub fn with_space<R>(&mut self, space_id: SpaceId, f: impl FnOnce(&mut Self) -> R) -> R {
+        let mut guard = self.enter_space(space_id);
+        f(&mut guard)
+    }
+
+    ///
+    /// Returns a facade over another space of the same app. The space of this facade is not
+    /// changed, which makes it the way to read across spaces.
+    ///
+    pub fn view_space(&self, space_id: &SpaceId) -> Self {
+        DescriptorFacade::new(self.storage.view_space(space_id))
+    }
+
+    ///
+    /// Looks up the preferred Descriptor for the point in each of the spaces, in the given order.
+    /// When the point is found in several spaces the precedence decides which results are kept.
+    ///
+    pub fn find_point_in_spaces(&self, point: &str, spaces: &[SpaceId], precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        let found: Vec<SpaceDescriptor> = spaces
+            .iter()
+            .filter_map(|space_id| {
+                self.view_space(space_id)
+                    .get_preferred_desc(point)
+                    .map(|desc| SpaceDescriptor { space_id: space_id.clone(), desc })
+            })
+            .collect();
+        Self::apply_space_precedence(found, precedence, self.get_point_matching())
+    }
+
+    ///
+    /// Looks up the point in all spaces of the app, in sorted space order.
+    ///
+    pub fn find_point_anywhere(&self, point: &str, precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.find_point_in_spaces(point, &self.list_spaces(), precedence)
+    }
+
+    ///
+    /// Returns the visible Descriptors of the spaces whose point, name, label or description
+    /// contains the query, ignoring case. Precedence is applied per point.
+    ///
+    pub fn search_in_spaces(&self, query: &str, spaces: &[SpaceId], precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        let query = query.to_lowercase();
+        let found: Vec<SpaceDescriptor> = spaces
+            .iter()
+            .flat_map(|space_id| {
+                self.view_space(space_id)
+                    .get_all_descs()
+                    .into_iter()
+                    .filter(|desc| Self::desc_contains(desc, &query))
+                    .map(|desc| SpaceDescriptor { space_id: space_id.clone(), desc })
+                    .collect::<Vec<SpaceDescriptor>>()
+            })
+            .collect();
+        Self::apply_space_precedence(found, precedence, self.get_point_matching())
+    }
+
+    pub fn search_anywhere(&self, query: &str, precedence: SpacePrecedence) -> Vec<SpaceDescriptor> {
+        self.search_in_spaces(query, &self.list_spaces(), precedence)
+    }
+
+    ///
+    /// Copies a Descriptor into another space and indexes it there. As desc_ids are content
+    /// addressed the copy keeps its desc_id. Copying into a space that already holds the
+    /// Descriptor changes nothing.
+    ///
+    pub fn copy_desc(&self, desc_id: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Descriptor, DescError> {
+        let from = self.view_existing_space(from_space)?;
+        let to = self.view_existing_space(to_space)?;
+        let desc = from.storage
+            .get_desc_by_id(desc_id)
+            .ok_or(DescError::DescNotFound(desc_id.to_string()))?;
+        to.add_desc_n_index(desc)
+    }
+
+    ///
+    /// Copies a Descriptor into another space and removes it from the space it came from.
+    ///
+    /// Fails with DescError::ReadOnly before copying when the source can not be written. When the
+    /// removal fails anyway, the copy is taken out of the target again.
+    ///
+    pub fn move_desc(&self, desc_id: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Descriptor, DescError> {
+        if from_space == to_space {
+            return self.copy_desc(desc_id, from_space, to_space);
+        }
+        let from = self.view_existing_space(from_space)?;
+        let to = self.view_existing_space(to_space)?;
+        if from.is_read_only() {
+            return Err(DescError::ReadOnly(from_space.to_string()));
+        }
+        let already_in_target = to.storage.get_desc_by_id(desc_id).is_some();
+        let desc = self.copy_desc(desc_id, from_space, to_space)?;
+        if let Err(err) = from.storage.remove_desc(desc_id) {
+            if !already_in_target {
+                let _ = to.storage.remove_desc(&desc.desc_id);
+            }
+            return Err(err);
+        }
+        Ok(desc)
+    }
+
+    ///
+    /// Copies all visible Descriptors of a space matching the query, the same way search_in_spaces
+    /// matches them. Stops at the first Descriptor that can not be copied.
+    ///
+    pub fn copy_descs(&self, query: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Vec<Descriptor>, DescError> {
+        self.search_in_spaces(query, std::slice::from_ref(from_space), SpacePrecedence::All)
+            .iter()
+            .map(|x| self.copy_desc(&x.desc.desc_id, from_space, to_space))
+            .collect()
+    }
+
+    ///
+    /// Moves all visible Descriptors of a space matching the query. Nothing is moved when either
+    /// space is read-only. Otherwise stops at the first Descriptor that can not be moved, leaving
+    /// the ones already moved in the target space; no Descriptor is left in both spaces.
+    ///
+    pub fn move_descs(&self, query: &str, from_space: &SpaceId, to_space: &SpaceId) -> Result<Vec<Descriptor>, DescError> {
+        for space_id in [from_space, to_space] {
+            if self.view_existing_space(space_id)?.is_read_only() {
+                return Err(DescError::ReadOnly(space_id.to_string()));
+            }
+        }
+        self.search_in_spaces(query, std::slice::from_ref(from_space), SpacePrecedence::All)
+            .iter()
+            .map(|x| self.move_desc(&x.desc.desc_id, from_space, to_space))
+            .collect()
+    }
+
+    ///
+    /// Compares the visible Descriptors of two spaces by point.
+    ///
+    pub fn diff_spaces(&self, space_a: &SpaceId, space_b: &SpaceId) -> Result<SpaceDiff, DescError> {
+        let a = self.view_existing_space(space_a)?;
+        let b = self.view_existing_space(space_b)?.get_all_descs();
+        Ok(diff_tools::diff_descs(&a.get_all_descs(), &b, a.get_point_matching()))
+    }
+
+    ///
+    /// Imports all visible Descriptors of the source space into the target space.
+    ///
+    /// Points only described in the source are added. Points described differently on both sides
+    /// are conflicts, resolved by the strategy. The target is ours and the source is theirs.
+    ///
+    /// Theirs removes every Descriptor indexed for the point in the target, also the ones the
+    /// point policy hides. A read-only target fails before anything is written. Descriptors a target using
+    /// PointPolicy::Reject refuses are listed in the report as rejected, the merge goes on.
+    ///
+    pub fn merge_space(&self, source_space: &SpaceId, target_space: &SpaceId, mut strategy: MergeStrategy) -> Result<MergeReport, DescError> {
+        let source = self.view_existing_space(source_space)?;
+        let target = self.view_existing_space(target_space)?;
+        if target.is_read_only() {
+            return Err(DescError::ReadOnly(target_space.to_string()));
+        }
+        let source_descs = source.get_all_descs();
+        let target_descs = target.get_all_descs();
+        let matching = target.get_point_matching();
+        let diff = diff_tools::diff_descs(&target_descs, &source_descs, matching);
+
+        let mut report = MergeReport::default();
+        let target_points: HashSet<String> = target_descs.iter().map(|x| matching.normalize(&x.point)).collect();
+        let source_points: HashSet<String> = source_descs.iter().map(|x| matching.normalize(&x.point)).collect();
+        report.unchanged = source_points.intersection(&target_points).count() - diff.changed.len();
+
+        for desc in diff.only_in_b {
+            match target.add_desc_n_index(desc.clone()) {
+                Ok(added) => report.added.push(added),
+                Err(DescError::PointAlreadyDescribed { .. }) => report.rejected.push(desc),
+                Err(err) => return Err(err),
+            }
+        }
+
+        for change in diff.changed {
+            let resolution = strategy.resolve(&change.a, &change.b);
+            let point = matching.normalize(&change.point);
+            let theirs: Vec<&Descriptor> = source_descs.iter().filter(|x| matching.normalize(&x.point) == point).collect();
+            if resolution == MergeResolution::Theirs {
+                let ours = target.get_descs_for_point(&change.point);
+                for desc in ours.iter().filter(|x| !theirs.iter().any(|y| y.desc_id == x.desc_id)) {
+                    target.storage.remove_desc(&desc.desc_id)?;
+                }
+            }
+            if resolution != MergeResolution::Ours {
+                for desc in theirs {
+                    match target.add_desc_n_index(desc.clone()) {
+                        Ok(_) => {}
+                        Err(DescError::PointAlreadyDescribed { .. }) => report.rejected.push(desc.clone()),
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            report.conflicts.push(MergeConflict {
+                point: change.point,
+                ours: change.a,
+                theirs: change.b,
+                resolution,
+            });
+        }
+        Ok(report)
+    }
+
+    fn view_existing_space(&self, space_id: &SpaceId) -> Result<Self, DescError> {
+        if self.get_space_info(space_id).is_none() {
+            return Err(DescError::SpaceNotFound(space_id.to_string()));
+        }
+        Ok(self.view_space(space_id))
+    }
+
+    fn desc_contains(desc: &Descriptor, lowercase_query: &str) -> bool {
+        [&desc.point, &desc.name, &desc.label, &desc.description]
+            .iter()
+            .any(|x| x.to_lowercase().contains(lowercase_query))
+    }
+
+    ///
+    /// Keeps the results of the first or last space for every point, or all results. Found
+    /// Descriptors are expected in space order. Points are compared by the matching.
+    ///
+    fn apply_space_precedence(found: Vec<SpaceDescriptor>, precedence: SpacePrecedence, matching: PointMatching) -> Vec<SpaceDescriptor> {
+        if precedence == SpacePrecedence::All {
+            return found;
+        }
+        let mut winners: HashMap<String, SpaceId> = HashMap::new();
+        for x in found.iter() {
+            if precedence == SpacePrecedence::LastSpaceWins {
+                winners.insert(matching.normalize(&x.desc.point), x.space_id.clone());
+            } else {
+                winners.entry(matching.normalize(&x.desc.point)).or_insert(x.space_id.clone());
+            }
+        }
+        found
+            .into_iter()
+            .filter(|x| winners.get(&matching.normalize(&x.desc.point)) == Some(&x.space_id))
+            .collect()
+    }
+
+    pub fn list_spaces(&self) -> Vec<SpaceId> {
+        self.storage.list_spaces()
+    }
+
+    ///
+    /// Returns a summary with counts for every space of the app.
+    ///
+    pub fn get_space_summaries(&self) -> Vec<SpaceSummary> {
+        self.list_spaces()
+            .iter()
+            .filter_map(|x| self.storage.get_space_summary(x))
+            .collect()
+    }
+
+    pub fn get_space_info(&self, space_id: &SpaceId) -> Option<SpaceInfo> {
+        self.storage.get_space_info(space_id)
+    }
+
+    pub fn get_current_space_info(&self) -> SpaceInfo {
+        self.storage.get_current_space_info()
+    }
+
+    pub fn set_space_info(&self, space_id: &SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.storage.set_space_info(space_id, info)
+    }
+
+    pub fn create_space(&self, space_id: SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.storage.create_space(space_id, info)
+    }
+
+    ///
+    /// Renames a space. Spaces inheriting from it are pointed to the new space id.
+    ///
+    pub fn rename_space(&self, space_id: SpaceId, new_space_id: SpaceId) -> Result<(), DescError> {
+        let children = self.get_child_spaces(&space_id);
+        self.storage.rename_space(space_id, new_space_id.clone())?;
+        for child in children {
+            if let Some(mut info) = self.get_space_info(&child) {
+                info.parent = Some(new_space_id.clone());
+                self.storage.set_space_info(&child, info)?;
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Deletes a space. A space other spaces inherit from can not be deleted and gives
+    /// DescError::SpaceInUse.
+    ///
+    pub fn delete_space(&self, space_id: SpaceId, confirmation: String) -> Result<(), DescError> {
+        if !self.get_child_spaces(&space_id).is_empty() {
+            return Err(DescError::SpaceInUse(space_id.to_string()));
+        }
+        self.storage.delete_space(space_id, confirmation)
+    }
+
+    ///
+    /// Returns the spaces naming the space as their parent.
+    ///
+    fn get_child_spaces(&self, space_id: &SpaceId) -> Vec<SpaceId> {
+        self.list_spaces()
+            .into_iter()
+            .filter(|x| self.get_space_info(x).and_then(|info| info.parent).as_ref() == Some(space_id))
+            .collect()
+    }
+
+    ///
+    /// Reads the point index as (point, desc_id) pairs and filters it by the point policy.
+    /// With LatestWins or FirstWins only one entry is kept per point, otherwise every entry is
+    /// kept.
+    ///
+    fn get_visible_index_entries(&self) -> Vec<(String, String)> {
+        self.iter_visible_index_entries().collect()
+    }
+
+    ///
+    /// Streams the visible (point, desc_id) pairs of the point index. FirstWins keeps the points
+    /// seen so far and LatestWins the latest desc_id of every point, reading the index twice, so
+    /// both hold one entry per distinct point. KeepAll and Reject pass the index through without
+    /// holding anything; add_desc_n_index never indexes a desc_id twice for a point, so only
+    /// hand-edited index files can repeat a line there.
+    ///
+    fn iter_visible_index_entries(&self) -> Box<dyn Iterator<Item = (String, String)> + '_> {
+        let matching = self.get_point_matching();
+        let entries = || {
+            self.storage
+                .iter_desc_point_indexes()
+                .filter_map(|line| {
+                    descriptor_tools::parse_desc_index_line(&line).map(|(p, id)| (p.to_string(), id.to_string()))
+                })
+        };
+
+        match self.get_point_policy() {
+            PointPolicy::FirstWins => {
+                let mut points: HashSet<String> = HashSet::new();
+                Box::new(entries().filter(move |x| points.insert(matching.normalize(&x.0))))
+            }
+            PointPolicy::LatestWins => {
+                let mut latest: HashMap<String, String> = entries()
+                    .map(|(point, id)| (matching.normalize(&point), id))
+                    .collect();
+                Box::new(entries().filter(move |x| {
+                    let point = matching.normalize(&x.0);
+                    latest.get(&point) == Some(&x.1) && latest.remove(&point).is_some()
+                }))
+            }
+            PointPolicy::KeepAll | PointPolicy::Reject => Box::new(entries()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+fn memory_facade(spaces: &[&str]) -> DescriptorFacade<super::memory_store::MemoryStore> {
+    let facade = DescriptorFacade::new(super::memory_store::MemoryStore::new(SpaceId::new(spaces[0]).unwrap()));
+    for space in spaces[1..].iter() {
+        facade.create_space(SpaceId::new(*space).unwrap(), SpaceInfo::default()).unwrap();
+    }
+    facade
+}
+
+#[cfg(test)]
+fn test_desc(point: &str, name: &str) -> Descriptor {
+    Descriptor { point: point.to_string(), name: name.to_string(), ..Default::default() }
+}
+
+#[test]
+fn move_desc_test() {
+    let facade = memory_facade(&["from", "to"]);
+    let (from, to) = (SpaceId::new("from").unwrap(), SpaceId::new("to").unwrap());
+    let ada = facade.view_space(&from).add_desc_n_index(test_desc("ada", "Ada")).unwrap();
+    let alan = facade.view_space(&from).add_desc_n_index(test_desc("alan", "Alan")).unwrap();
+
+    facade.storage.fail_removals(&from);
+    assert!(matches!(facade.move_desc(&ada.desc_id, &from, &to), Err(DescError::Io(_))));
+    assert!(facade.view_space(&to).get_all_descs().is_empty());
+    assert_eq!(facade.view_space(&from).get_all_descs().len(), 2);
+
+    facade.copy_desc(&ada.desc_id, &from, &to).unwrap();
+    assert!(facade.move_desc(&ada.desc_id, &from, &to).is_err());
+    assert_eq!(facade.view_space(&to).get_all_descs(), vec![ada.clone()]);
+
+    let facade = memory_facade(&["from", "to"]);
+    facade.view_space(&from).add_desc_n_index(ada.clone()).unwrap();
+    facade.view_space(&from).add_desc_n_index(alan.clone()).unwrap();
+    facade.copy_desc(&ada.desc_id, &from, &to).unwrap();
+    facade.move_desc(&ada.desc_id, &from, &to).unwrap();
+    assert_eq!(facade.view_space(&to).get_all_descs(), vec![ada.clone()]);
+    assert_eq!(facade.view_space(&from).get_all_descs(), vec![alan.clone()]);
+
+    let to_view = facade.view_space(&to);
+    to_view.set_point_policy(PointPolicy::Reject).unwrap();
+    to_view.add_desc_n_index(test_desc("alan", "Alan Turing")).unwrap();
+    assert!(matches!(facade.move_desc(&alan.desc_id, &from, &to), Err(DescError::PointAlreadyDescribed { .. })));
+    assert_eq!(facade.view_space(&from).get_all_descs(), vec![alan.clone()]);
+}
+
+#[test]
+fn move_descs_test() {
+    let facade = memory_facade(&["from", "to"]);
+    let (from, to) = (SpaceId::new("from").unwrap(), SpaceId::new("to").unwrap());
+    let ada = facade.view_space(&from).add_desc_n_index(test_desc("ada", "Ada")).unwrap();
+    let alan = facade.view_space(&from).add_desc_n_index(test_desc("alan", "Alan")).unwrap();
+
+    assert_eq!(facade.copy_descs("a", &from, &to).unwrap().len(), 2);
+    assert_eq!(facade.view_space(&from).get_all_descs().len(), 2);
+
+    let facade = memory_facade(&["from", "to"]);
+    facade.view_space(&from).add_desc_n_index(ada.clone()).unwrap();
+    facade.view_space(&from).add_desc_n_index(alan.clone()).unwrap();
+    let mut info = facade.get_space_info(&from).unwrap();
+    info.read_only = true;
+    facade.set_space_info(&from, info.clone()).unwrap();
+    assert!(matches!(facade.move_descs("a", &from, &to), Err(DescError::ReadOnly(_))));
+    assert!(facade.view_space(&to).get_all_descs().is_empty());
+
+    info.read_only = false;
+    facade.set_space_info(&from, info).unwrap();
+    assert_eq!(facade.move_descs("ada", &from, &to).unwrap(), vec![ada.clone()]);
+    assert_eq!(facade.view_space(&from).get_all_descs(), vec![alan]);
+    assert_eq!(facade.view_space(&to).get_all_descs(), vec![ada]);
+}
+
+#[test]
+fn parent_space_rename_delete_test() {
+    let facade = memory_facade(&["child", "org"]);
+    let space = |x: &str| SpaceId::new(x).unwrap();
+    facade.view_space(&space("org")).add_desc_n_index(test_desc("shared", "Shared")).unwrap();
+    facade.set_space_parent(&space("child"), Some(space("org"))).unwrap();
+
+    facade.rename_space(space("org"), space("org2")).unwrap();
+    assert_eq!(facade.get_space_info(&space("child")).unwrap().parent, Some(space("org2")));
+    assert_eq!(facade.get_inherited_desc("shared").map(|x| x.space_id), Some(space("org2")));
+
+    assert_eq!(facade.delete_space(space("org2"), "org2".to_string()), Err(DescError::SpaceInUse("org2".to_string())));
+    facade.set_space_parent(&space("child"), None).unwrap();
+    facade.delete_space(space("org2"), "org2".to_string()).unwrap();
 }
 
+#[test]
+fn merge_space_test() {
+    let facade = memory_facade(&["target", "source"]);
+    let space = |x: &str| SpaceId::new(x).unwrap();
+    let (target, source) = (facade.view_space(&space("target")), facade.view_space(&space("source")));
+    target.set_point_policy(PointPolicy::FirstWins).unwrap();
+    target.add_desc_n_index(test_desc("p", "x1")).unwrap();
+    target.add_desc_n_index(test_desc("p", "x2")).unwrap();
+    let theirs = source.add_desc_n_index(test_desc("p", "theirs")).unwrap();
+    let added = source.add_desc_n_index(test_desc("q", "new")).unwrap();
+
+    let report = facade.merge_space(&space("source"), &space("target"), MergeStrategy::Theirs).unwrap();
+    assert_eq!(report.added, vec![added]);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(target.get_preferred_desc("p"), Some(theirs.clone()));
+    assert_eq!(target.get_descs_for_point("p"), vec![theirs]);
+
+    let facade = memory_facade(&["target", "source"]);
+    let (target, source) = (facade.view_space(&space("target")), facade.view_space(&space("source")));
+    target.set_point_policy(PointPolicy::Reject).unwrap();
+    let ours = target.add_desc_n_index(test_desc("p", "ours")).unwrap();
+    let theirs = source.add_desc_n_index(test_desc("p", "theirs")).unwrap();
+    let report = facade.merge_space(&space("source"), &space("target"), MergeStrategy::KeepBoth).unwrap();
+    assert_eq!(report.rejected, vec![theirs]);
+    assert_eq!(target.get_descs_for_point("p"), vec![ours]);
+}
+
+#[test]
+fn list_filter_test() {
+    let facade = memory_facade(&["org"]);
+    let mut desc = test_desc("p", "Lines");
+    desc.description = "line one\nline two".to_string();
+    facade.add_desc_n_index(desc).unwrap();
+    facade.add_desc_n_index(test_desc("q", "Other")).unwrap();
+
+    let list = |query: &str| {
+        let options = ListOptions { filter: Some(DescQuery::parse(query).unwrap()), ..Default::default() };
+        facade.list_descs(&options).iter().map(|x| x.desc.point.clone()).collect::<Vec<String>>()
+    };
+    let query = |query: &str| {
+        facade.query_descs(&DescQuery::parse(query).unwrap()).iter().map(|x| x.point.clone()).collect::<Vec<String>>()
+    };
+    for filter in [r#"desc:"*one line*""#, r#"desc:"*one*two""#, "name:Other"] {
+        assert_eq!(list(filter), query(filter), "{}", filter);
+    }
+    assert!(list(r#"desc:"*one line*""#).is_empty());
+    assert_eq!(list(r#"desc:"*one*two""#), vec!["p"]);
+}
+
+#[test]
+fn iter_descs_by_label_test() {
+    let facade = memory_facade(&["org"]);
+    let mut ada = test_desc("ada", "Ada");
+    ada.label = "person".to_string();
+    let ada = facade.add_desc_n_index(ada).unwrap();
+    facade.add_desc_n_index(test_desc("engine", "Engine")).unwrap();
+    facade.set_point_policy(PointPolicy::FirstWins).unwrap();
+    let mut later = test_desc("ada", "Ada later");
+    later.label = "person".to_string();
+    facade.add_desc_n_index(later).unwrap();
+
+    assert_eq!(facade.iter_descs_by_label("person").collect::<Vec<Descriptor>>(), vec![ada]);
+    assert_eq!(facade.iter_descs_by_label("").count(), 1);
+}
+
+#[test]
+fn space_stats_matching_test() {
+    let facade = memory_facade(&["org"]);
+    facade.set_point_matching(PointMatching::CaseFold).unwrap();
+    facade.add_desc_n_index(test_desc("Cafe", "upper")).unwrap();
+    facade.add_desc_n_index(test_desc("cafe", "lower")).unwrap();
+
+    let stats = facade.get_space_stats();
+    assert_eq!((stats.desc_count, stats.distinct_points, stats.duplicates), (2, 1, 1));
+    assert_eq!(facade.get_descs_for_point("CAFE").len(), 2);
+    assert_eq!(facade.get_space_summaries()[0].point_count, 1);
+}
+
+#[test]
+fn get_descs_hashmap_for_list_test() {
+    let facade = memory_facade(&["child", "org"]);
+    let space = |x: &str| SpaceId::new(x).unwrap();
+    facade.set_point_matching(PointMatching::UnicodeFold).unwrap();
+    let p = facade.add_desc_n_index(test_desc("p", "P")).unwrap();
+    let cafe = facade.add_desc_n_index(test_desc("Cafe", "Cafe")).unwrap();
+    let org = facade.view_space(&space("org"));
+    let shared = org.add_desc_n_index(test_desc("shared", "Shared")).unwrap();
+    org.add_desc_n_index(test_desc("shared", "Shared later")).unwrap();
+    facade.set_space_parent(&space("child"), Some(space("org"))).unwrap();
+
+    let list = ["p", "p", "Café", "cafe", "shared", "shared", "none"].map(String::from).to_vec();
+    let result = facade.get_descs_hashmap_for_list(list);
+    assert_eq!(result.len(), 5);
+    assert_eq!(result["p"], vec![p]);
+    assert_eq!(result["Café"], vec![cafe.clone()]);
+    assert_eq!(result["cafe"], vec![cafe]);
+    assert_eq!(result["shared"], vec![shared]);
+    assert_eq!(result["none"], vec![Descriptor { point: "none".to_string(), ..Default::default() }]);
+}
@@ -1,4 +1,5 @@
-use crate::Descriptor;
+use crate::{Descriptor, DescError, PointMatching, PointPolicy, SpaceId, SpaceInfo, SpaceSummary};
+use super::space_guard::SpaceGuard;
 
 pub trait DescriptorStore {
 
@@ -12,11 +13,38 @@ fn get_descs_or_else_ids(&self, points: Vec<String>) -> Vec<Descriptor>;
 
 fn get_all_descs(&self) -> Vec<Descriptor>;
 
-fn add_desc(&self, desc: Descriptor, id: String);
+///
+/// Returns every Descriptor indexed for the point, in the order they were indexed.
+///
+fn get_descs_for_point(&self, point: &str) -> Vec<Descriptor>;
+
+///
+/// Loads a Descriptor by its desc_id, if it is stored in the current space.
+///
+fn get_desc_by_id(&self, desc_id: &str) -> Option<Descriptor>;
+
+fn add_desc(&self, desc: Descriptor, id: String) -> Result<(), DescError>;
+
+///
+/// Deletes a Descriptor and all its index lines from the current space.
+///
+fn remove_desc(&self, desc_id: &str) -> Result<(), DescError>;
 
 fn get_desc_point_indexes(&self) -> String;
 
-fn get_tmp_space_desc_point_indexes(&mut self, space_id: String) -> String;
+///
+/// Returns the lines of the point index one by one. Stores that can read the index lazily
+/// should, so scans over large spaces do not hold the whole index in memory.
+///
+fn iter_desc_point_indexes(&self) -> Box<dyn Iterator<Item = String>> {
+    let lines: Vec<String> = self.get_desc_point_indexes().lines().map(String::from).collect();
+    Box::new(lines.into_iter())
+}
+
+///
+/// Returns the point index of another space without switching the space in use.
+///
+fn get_tmp_space_desc_point_indexes(&self, space_id: SpaceId) -> String;
 
 fn get_desc_name_indexes(&self) -> String;
 
@@ -24,25 +52,131 @@ fn get_desc_label_indexes(&self) -> String;
 
 fn get_desc_description_indexes(&self) -> String;
 
+///
+/// Returns a value that changes whenever the indexes or settings of the space in use change,
+/// so derived data can be cached until then.
+///
+fn get_index_fingerprint(&self) -> u64;
+
 
-fn set_desc_point_indexes(&self, indexes: &str);
+fn set_desc_point_indexes(&self, indexes: &str) -> Result<(), DescError>;
 
-fn set_desc_name_indexes(&self, indexes: &str);
+fn set_desc_name_indexes(&self, indexes: &str) -> Result<(), DescError>;
 
-fn set_desc_label_indexes(&self, indexes: &str);
+fn set_desc_label_indexes(&self, indexes: &str) -> Result<(), DescError>;
 
-fn set_desc_description_indexes(&self, indexes:&str);
+fn set_desc_description_indexes(&self, indexes:&str) -> Result<(), DescError>;
 
 
-fn set_tmp_space_id(&mut self, space_id: String);
+fn set_tmp_space_id(&mut self, space_id: SpaceId);
 
 fn revert_space_id(&mut self);
 
-fn get_space_id(&mut self) -> String;
+fn get_space_id(&self) -> SpaceId;
 
 ///
-/// Root function for adding indexes for a Descriptor.
+/// Returns the temporary space set with set_tmp_space_id, or None while the original space is
+/// used.
+///
+fn get_tmp_space_id(&self) -> Option<SpaceId>;
+
+///
+/// Returns a store for another space, leaving this one untouched. Meant for reading across
+/// spaces with &self, no folders are created for the space.
+///
+fn view_space(&self, space_id: &SpaceId) -> Self where Self: Sized;
+
+///
+/// Switches to a temporary space until the returned guard is dropped.
+///
+fn enter_space(&mut self, space_id: SpaceId) -> SpaceGuard<'_, Self> where Self: Sized {
+    SpaceGuard::new(self, space_id)
+}
+
+///
+/// Runs the closure with the store switched to a temporary space. The space used before is
+/// restored afterwards, also when the closure panics.
+///
+fn with_space<R, F>(&mut self, space_id: SpaceId, f: F) -> R
+where
+    Self: Sized,
+    F: FnOnce(&mut Self) -> R,
+{
+    let mut guard = self.enter_space(space_id);
+    f(&mut guard)
+}
+
+///
+/// Returns every Descriptor indexed for the point in another space.
+///
+fn get_space_descs_for_point(&self, space_id: &SpaceId, point: &str) -> Vec<Descriptor> where Self: Sized {
+    self.view_space(space_id).get_descs_for_point(point)
+}
+
 ///
-fn index_desc(&self, desc: Descriptor);
+/// Returns all Descriptors of another space.
+///
+fn get_space_all_descs(&self, space_id: &SpaceId) -> Vec<Descriptor> where Self: Sized {
+    self.view_space(space_id).get_all_descs()
+}
+
+///
+/// Returns the policy for descriptors sharing a point in the current space.
+///
+fn get_point_policy(&self) -> PointPolicy;
+
+fn set_point_policy(&self, policy: PointPolicy) -> Result<(), DescError>;
+
+///
+/// Returns how points are compared in the current space.
+///
+fn get_point_matching(&self) -> PointMatching {
+    self.get_current_space_info().point_matching
+}
+
+fn set_point_matching(&self, matching: PointMatching) -> Result<(), DescError>;
+
+///
+/// Tells if writes to the space in use fail, because the store or the space is read-only.
+///
+fn is_read_only(&self) -> bool;
+
+
+// Space management for all spaces of the app.
+
+///
+/// Returns the ids of all spaces of the app, sorted. Folders that are not valid space ids are
+/// left out.
+///
+fn list_spaces(&self) -> Vec<SpaceId>;
+
+fn get_space_info(&self, space_id: &SpaceId) -> Option<SpaceInfo>;
+
+///
+/// Returns the metadata of the space in use, or the defaults if it has none.
+///
+fn get_current_space_info(&self) -> SpaceInfo {
+    self.get_space_info(&self.get_space_id()).unwrap_or_default()
 }
 
+///
+/// Returns the space with counts of its content, if the space exists.
+///
+fn get_space_summary(&self, space_id: &SpaceId) -> Option<SpaceSummary>;
+
+fn create_space(&self, space_id: SpaceId, info: SpaceInfo) -> Result<(), DescError>;
+
+fn set_space_info(&self, space_id: &SpaceId, info: SpaceInfo) -> Result<(), DescError>;
+
+fn rename_space(&self, space_id: SpaceId, new_space_id: SpaceId) -> Result<(), DescError>;
+
+///
+/// Deletes a space with all its content. The confirmation has to repeat the space id.
+///
+fn delete_space(&self, space_id: SpaceId, confirmation: String) -> Result<(), DescError>;
+
+///
+/// Root function for adding indexes for a Descriptor.
+///
+fn index_desc(&self, desc: Descriptor) -> Result<(), DescError>;
+}
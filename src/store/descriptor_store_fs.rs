@@ -1,6 +1,9 @@
 
-use crate::{Descriptor, model::{space::Space, app::App}};
-use std::{fs, path::Path};
+use crate::{AppName, Descriptor, DescError, PointMatching, PointPolicy, SpaceId, SpaceInfo, SpaceSummary};
+use crate::misc::descriptor_tools;
+use std::{collections::HashSet, fs, path::Path};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, BufReader};
 use super::{descriptor_store::DescriptorStore, descriptor_facade::{DescIndex, self}};
 use ig_tools::file_tools;
 
@@ -18,8 +21,10 @@ struct DescConfig {
     space_folder_name: String,
     desc_folder_name: String,
     index_folder_name: String,
-    org_space: Space,
-    tmp_space: Space,
+    #[serde(skip)]
+    org_space: SpaceId,
+    #[serde(skip)]
+    tmp_space: Option<SpaceId>,
 }
 
 impl ::std::default::Default for DescConfig {
@@ -30,8 +35,8 @@ impl ::std::default::Default for DescConfig {
             space_folder_name: "spaces".to_string(),
             desc_folder_name: "descs".to_string(),
             index_folder_name: "indexes".to_string(),
-            org_space: Space::from("default".to_string()),
-            tmp_space: Space::Option(None),
+            org_space: SpaceId::default(),
+            tmp_space: None,
         }
     }
 }
@@ -44,6 +49,7 @@ pub struct DescriptorStoreFS {
     space_folder_path: PathBuf,
     desc_folder_path: PathBuf,
     index_folder_path: PathBuf,
+    read_only: bool,
 }
 
 impl ::std::default::Default for DescriptorStoreFS {
@@ -54,6 +60,7 @@ impl ::std::default::Default for DescriptorStoreFS {
             desc_folder_path: PathBuf::new(),
             index_folder_path: PathBuf::new(),
             space_folder_path: PathBuf::new(),
+            read_only: false,
         }
     }
 }
@@ -62,40 +69,53 @@ impl DescriptorStoreFS {
 ///
 /// Create a new DescriptorStoreFS. 
 ///
-/// The parameter app_name is optionally the name of the
-/// application which then will be the name of the app folder containing the data.
-/// If no name is given "infospace" is used as a catch all folder.
+/// The parameter app_name is the name of the application which then will be the name of the
+/// app folder containing the data. AppName::default() gives "infospace", used as a catch all
+/// folder.
 /// 
-/// Parameter space_id is an optional space that data is to be stored or retrieved from, per
-/// default. If no space parameter is given the data will be stored in the root desc and index
-/// folders of the app folder. 
+/// Parameter space_id is the space that data is to be stored or retrieved from, per default.
+/// SpaceId::default() gives the space "default".
 ///
 /// Parameter desc_config is an optional name for a configuration file storing all path and folder
 /// name variables used to setup the DescriptorStoreFS. If the desc_config parameter is set the
 /// method will search for it in the standard configuration folder of your Operative System.
 /// If no configuration file is given, a default naming will be used by calling the Default trait
 /// for the DescConfig struct.
-    pub fn new(app_name: App, space_id: Space, config_name: String) -> Self {
+///
+/// A configuration file that can not be read or parsed gives DescError::Config.
+    pub fn new(app_name: AppName, space_id: SpaceId, config_name: String) -> Result<Self, DescError> {
+        Self::open(app_name, space_id, config_name, false)
+    }
 
-        let mut config: DescConfig = confy::load(config_name.as_str(), None).unwrap();
-        
-        if app_name.get_value().is_none() {
-            config.app_folder_name = "infospace".to_string();    
-        } else {
-            config.app_folder_name = app_name.to_string();    
-        }
+    ///
+    /// Create a new DescriptorStoreFS that refuses all writes. Nothing is created on the file
+    /// system, neither folders nor a missing configuration file.
+    ///
+    pub fn new_read_only(app_name: AppName, space_id: SpaceId, config_name: String) -> Result<Self, DescError> {
+        Self::open(app_name, space_id, config_name, true)
+    }
 
-        if space_id.get_value().is_none() {
-            config.org_space = Space::from("default".to_string());    
+    fn open(app_name: AppName, space_id: SpaceId, config_name: String, read_only: bool) -> Result<Self, DescError> {
+
+        let config_exists = confy::get_configuration_file_path(config_name.as_str(), None)
+            .map(|x| x.is_file())
+            .unwrap_or(false);
+        let mut config: DescConfig = if read_only && !config_exists {
+            DescConfig::default()
         } else {
-            config.org_space = space_id.clone();    
-        }
+            confy::load(config_name.as_str(), None).map_err(|x| DescError::Config(x.to_string()))?
+        };
+        
+        config.app_folder_name = app_name.to_string();    
+        config.org_space = space_id;
+        config.tmp_space = None;
 
         let mut instance: DescriptorStoreFS = DescriptorStoreFS::default();
         instance.config = config;
+        instance.read_only = read_only;
         Self::init_folders(&mut instance);
 
-        instance
+        Ok(instance)
     }
 
     ///
@@ -104,24 +124,41 @@ impl DescriptorStoreFS {
     ///
     /// Creates the folders if not already there. The default parent folder is the default app data
     /// folder of the Operative System running the application.
+    /// A read-only instance only sets the paths.
     ///    
     fn init_folders(&mut self) {
 
         let desc_config: DescConfig = self.clone().config;
 
-        let data_dir = desc_config.app_parent_path.clone();
-        let _ = data_dir.join(desc_config.app_folder_name.clone());
-        self.app_folder_path = data_dir.clone();
-
-        let _ = data_dir.join(desc_config.space_folder_name.clone());
-        let _ = data_dir.join(self.get_space_id());
-        self.space_folder_path = data_dir.clone();
+        self.set_folder_paths();
+        if self.read_only {
+            return;
+        }
+        let data_dir = self.space_folder_path.clone();
         let _ = fs::create_dir_all(data_dir.clone());
         
         self.create_desc_folder_in_folder(desc_config.clone(), data_dir.clone());
 
         self.create_index_folder_in_folder(desc_config.clone(), data_dir.clone());
-        
+
+        if !self.get_space_info_path(&self.get_space_id()).is_file() {
+            let _ = self.write_space_info(&self.get_space_id(), &SpaceInfo::new(""));
+        }
+    }
+
+    ///
+    /// Sets all the folder path variables for the space in use, without touching the file system.
+    ///
+    fn set_folder_paths(&mut self) {
+        let app_dir = self.config.app_parent_path.join(self.config.app_folder_name.clone());
+        self.app_folder_path = app_dir.clone();
+
+        let data_dir = app_dir
+            .join(self.config.space_folder_name.clone())
+            .join(self.get_space_id().as_str());
+        self.space_folder_path = data_dir.clone();
+        self.desc_folder_path = data_dir.join(self.config.desc_folder_name.clone());
+        self.index_folder_path = data_dir.join(self.config.index_folder_name.clone());
     }
 
     ///
@@ -135,7 +172,7 @@ impl DescriptorStoreFS {
     }
 
     ///
-    /// Used to create a folder for indexes, along with sub folders for specific indexes.
+    /// Used to create a folder for indexes, along with empty files for the specific indexes.
     ///
     fn create_index_folder_in_folder(&mut self, config: DescConfig, parent: PathBuf) {
         
@@ -143,17 +180,10 @@ impl DescriptorStoreFS {
         self.index_folder_path = index_folder_dir.clone();
         let _ = fs::create_dir_all(index_folder_dir.clone());
     
-        let index_dir = index_folder_dir.join(DescIndex::DescPointIndex.to_string());
-        let _ = fs::create_dir_all(index_dir);
-
-        let index_dir = index_folder_dir.join(DescIndex::DescNameIndex.to_string());
-        let _ = fs::create_dir_all(index_dir);
-
-        let index_dir = index_folder_dir.join(DescIndex::DescLabelIndex.to_string());
-        let _ = fs::create_dir_all(index_dir);
-
-        let index_dir = index_folder_dir.join(DescIndex::DescDescIndex.to_string());
-        let _ = fs::create_dir_all(index_dir);
+        Self::create_file_if_not_there(DescIndex::DescPointIndex.to_string(), index_folder_dir.clone());
+        Self::create_file_if_not_there(DescIndex::DescNameIndex.to_string(), index_folder_dir.clone());
+        Self::create_file_if_not_there(DescIndex::DescLabelIndex.to_string(), index_folder_dir.clone());
+        Self::create_file_if_not_there(DescIndex::DescDescIndex.to_string(), index_folder_dir.clone());
 
     }
 
@@ -181,6 +211,54 @@ impl DescriptorStoreFS {
         self.index_folder_path.clone().join(index.to_string())
     }
 
+    ///
+    /// Composes the file system path for the folder of any space of the app.
+    ///
+    fn get_space_folder_path(&self, space_id: &SpaceId) -> PathBuf {
+        self.app_folder_path.join(&self.config.space_folder_name).join(space_id.as_str())
+    }
+
+    ///
+    /// Composes the file system path for the metadata file of a space.
+    ///
+    fn get_space_info_path(&self, space_id: &SpaceId) -> PathBuf {
+        self.get_space_folder_path(space_id).join("space_info.json")
+    }
+
+    ///
+    /// A space is in use when it is either the original or the temporary space of this instance.
+    ///
+    fn is_space_in_use(&self, space_id: &SpaceId) -> bool {
+        self.get_space_id() == *space_id || self.config.org_space == *space_id
+    }
+
+    ///
+    /// Fails if this instance was opened read-only.
+    ///
+    fn check_store_writable(&self) -> Result<(), DescError> {
+        if self.read_only {
+            return Err(DescError::ReadOnly(self.get_space_id().to_string()));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Fails if this instance was opened read-only or the space is marked read-only.
+    ///
+    fn check_space_writable(&self, space_id: &SpaceId) -> Result<(), DescError> {
+        self.check_store_writable()?;
+        if self.get_space_info(space_id).is_some_and(|x| x.read_only) {
+            return Err(DescError::ReadOnly(space_id.to_string()));
+        }
+        Ok(())
+    }
+
+    fn write_space_info(&self, space_id: &SpaceId, info: &SpaceInfo) -> Result<(), DescError> {
+        let content = serde_json::to_string_pretty(info).map_err(|x| DescError::Io(x.to_string()))?;
+        fs::write(self.get_space_info_path(space_id), content)?;
+        Ok(())
+    }
+
     ///
     /// As the name implies this method loads a descriptor note from the file system.
     /// It does so after composing the path to the file, based on its parameter desc_id.
@@ -195,14 +273,16 @@ impl DescriptorStoreFS {
 
 
     ///
-    /// Create an index line and adds it to an index.
+    /// Create an index line and adds it to an index. Index lines hold the value first and the
+    /// desc_id last, the same way descriptor_tools::create_desc_index_line composes them.
+    /// Line breaks in the value are replaced by spaces so every line holds one entry.
     /// This method is very general and therefore useful as helper method when appending to
     /// multiple indexes.
     ///
     fn append_index(id: String, value: String, index: String) -> String {
-        let mut line: String = id;
+        let mut line: String = value.trim().replace(['\n', '\r'], " ");
         line.push(' ');
-        line.push_str(value.as_str());
+        line.push_str(id.as_str());
         let mut result: String = index.clone();
         if !result.is_empty() {    
             result.push('\n');
@@ -223,8 +303,8 @@ impl DescriptorStore for DescriptorStoreFS {
     ///
     /// Sets a temporary space id. May be useful for smaller operations as a new DescriptorStore instance does not have to be made. 
     ///
-    fn set_tmp_space_id(&mut self, space_id: String) {
-        self.config.tmp_space = Space::from(space_id.clone());
+    fn set_tmp_space_id(&mut self, space_id: SpaceId) {
+        self.config.tmp_space = Some(space_id);
         Self::init_folders(self);
     }
 
@@ -233,7 +313,7 @@ impl DescriptorStore for DescriptorStoreFS {
     /// the used space id to the original one.
     ///
     fn revert_space_id(&mut self) {
-        self.config.tmp_space = Space::from(self.config.org_space.clone());
+        self.config.tmp_space = None;
         Self::init_folders(self);
     }
 
@@ -242,16 +322,150 @@ impl DescriptorStore for DescriptorStoreFS {
     /// was created, or it may be a temporary space id set explicitly by a call to
     /// the function set_tmp_space_id.
     ///
-    fn get_space_id(&mut self) -> String {
-        if self.config.tmp_space.is_none() || self.config.org_space.get_value() == self.config.tmp_space.get_value() {
-            return self.config.org_space.get_value().unwrap();
-        }
-        self.config.tmp_space.get_value().unwrap()
+    fn get_space_id(&self) -> SpaceId {
+        self.config.tmp_space.clone().unwrap_or_else(|| self.config.org_space.clone())
+    }
+
+    fn get_tmp_space_id(&self) -> Option<SpaceId> {
+        self.config.tmp_space.clone()
+    }
+
+    ///
+    /// Returns a copy of this instance reading from and writing to another space. Unlike
+    /// set_tmp_space_id no folders are created, so reading a space that does not exist returns
+    /// nothing.
+    ///
+    fn view_space(&self, space_id: &SpaceId) -> Self {
+        let mut view = self.clone();
+        view.config.tmp_space = Some(space_id.clone());
+        view.set_folder_paths();
+        view
     }
 
     // |dynamic space handling end|
 
 
+    // |space management begin|
+
+    fn list_spaces(&self) -> Vec<SpaceId> {
+        let spaces_dir = self.app_folder_path.join(&self.config.space_folder_name);
+        let mut spaces: Vec<SpaceId> = fs::read_dir(spaces_dir)
+            .map(|entries| entries
+                .filter_map(|x| x.ok())
+                .filter(|x| x.path().is_dir())
+                .filter_map(|x| x.file_name().into_string().ok())
+                .filter_map(|x| SpaceId::new(x).ok())
+                .collect())
+            .unwrap_or_default();
+        spaces.sort();
+        spaces
+    }
+
+    ///
+    /// Reads the metadata file of a space. A space without one gets the defaults. A file that can
+    /// not be parsed gives read-only defaults, so a broken file never makes a space writable;
+    /// set_space_info replaces it.
+    ///
+    fn get_space_info(&self, space_id: &SpaceId) -> Option<SpaceInfo> {
+        if !self.get_space_folder_path(space_id).is_dir() {
+            return None;
+        }
+        let info = match fs::read_to_string(self.get_space_info_path(space_id)) {
+            Ok(content) => serde_json::from_str(&content)
+                .unwrap_or_else(|_| SpaceInfo { read_only: true, ..Default::default() }),
+            Err(_) => SpaceInfo::default(),
+        };
+        Some(info)
+    }
+
+    fn get_space_summary(&self, space_id: &SpaceId) -> Option<SpaceSummary> {
+        let info = self.get_space_info(space_id)?;
+        let point_indexes = fs::read_to_string(
+            self.get_space_folder_path(space_id)
+                .join(&self.config.index_folder_name)
+                .join(DescIndex::DescPointIndex.to_string())
+        ).unwrap_or_default();
+
+        let entries: Vec<(&str, &str)> = point_indexes.lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .collect();
+        let points: HashSet<String> = entries.iter().map(|x| info.point_matching.normalize(x.0)).collect();
+        let ids: HashSet<&str> = entries.iter().map(|x| x.1).collect();
+
+        Some(SpaceSummary {
+            space_id: space_id.clone(),
+            info,
+            desc_count: ids.len(),
+            point_count: points.len(),
+        })
+    }
+
+    ///
+    /// Creates the folders and metadata file for a new space. The current space is not changed.
+    ///
+    fn create_space(&self, space_id: SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.check_store_writable()?;
+        if self.get_space_folder_path(&space_id).exists() {
+            return Err(DescError::SpaceAlreadyExists(space_id.to_string()));
+        }
+        let mut space = self.clone();
+        space.config.tmp_space = Some(space_id.clone());
+        space.init_folders();
+        space.write_space_info(&space_id, &info)
+    }
+
+    ///
+    /// Replaces the metadata of a space. This works on read-only spaces too, so they can be made
+    /// writable again.
+    ///
+    fn set_space_info(&self, space_id: &SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        self.check_store_writable()?;
+        if !self.get_space_folder_path(space_id).is_dir() {
+            return Err(DescError::SpaceNotFound(space_id.to_string()));
+        }
+        self.write_space_info(space_id, &info)
+    }
+
+    ///
+    /// Renames the folder of a space. As desc_ids do not depend on the space nothing inside the
+    /// space has to change. The space currently used can not be renamed.
+    ///
+    fn rename_space(&self, space_id: SpaceId, new_space_id: SpaceId) -> Result<(), DescError> {
+        let from = self.get_space_folder_path(&space_id);
+        let to = self.get_space_folder_path(&new_space_id);
+        if !from.is_dir() {
+            return Err(DescError::SpaceNotFound(space_id.to_string()));
+        }
+        if to.exists() {
+            return Err(DescError::SpaceAlreadyExists(new_space_id.to_string()));
+        }
+        if self.is_space_in_use(&space_id) {
+            return Err(DescError::SpaceInUse(space_id.to_string()));
+        }
+        self.check_space_writable(&space_id)?;
+        fs::rename(from, to)?;
+        Ok(())
+    }
+
+    fn delete_space(&self, space_id: SpaceId, confirmation: String) -> Result<(), DescError> {
+        let space_dir = self.get_space_folder_path(&space_id);
+        if !space_dir.is_dir() {
+            return Err(DescError::SpaceNotFound(space_id.to_string()));
+        }
+        if confirmation != space_id.as_str() {
+            return Err(DescError::ConfirmationMismatch(space_id.to_string()));
+        }
+        if self.is_space_in_use(&space_id) {
+            return Err(DescError::SpaceInUse(space_id.to_string()));
+        }
+        self.check_space_writable(&space_id)?;
+        fs::remove_dir_all(space_dir)?;
+        Ok(())
+    }
+
+    // |space management end|
+
+
 
     fn get_descs(&self, points: Vec<&str>) -> Vec<Descriptor> {
         points.iter().map(|x|self.get_desc(x)).collect()
@@ -263,9 +477,11 @@ impl DescriptorStore for DescriptorStoreFS {
         let lines = binding.lines();
         let mut descs: Vec<Descriptor> = Vec::new();
 
-        let filenames: Vec<&str> = lines.map(|x|{x.split_once(' ').unwrap().1}).collect();
+        let filenames: Vec<&str> = lines.filter_map(|x|{descriptor_tools::parse_desc_index_line(x)}).map(|x| x.1).collect();
         for filename in filenames {
-            descs.push(Descriptor::from(self.load_desc(filename)));
+            let mut desc = Descriptor::from(self.load_desc(filename));
+            desc.set_desc_id(filename);
+            descs.push(desc);
         };
 
         descs
@@ -279,8 +495,10 @@ impl DescriptorStore for DescriptorStoreFS {
     fn get_desc_or_id(&self, name: &str) -> Descriptor {
         let binding = self.get_desc_point_indexes();
         let mut lines = binding.lines();
+        let matching = self.get_point_matching();
+        let wanted = matching.normalize(name);
 
-        let point = lines.find_map(|x|{ let y = x.split_once(" ").unwrap(); if y.0 == name {return Some(y.1)}else{ return None}});
+        let point = lines.find_map(|x|{ let y = descriptor_tools::parse_desc_index_line(x)?; if matching.normalize(y.0) == wanted { Some(y.1) } else { None }});
 
         let mut content = "".to_string();
         if point.is_some(){
@@ -302,11 +520,13 @@ impl DescriptorStore for DescriptorStoreFS {
     fn get_desc(&self, name: &str) -> Descriptor {
         let binding = self.get_desc_point_indexes();
         let mut lines = binding.lines();
+        let matching = self.get_point_matching();
+        let wanted = matching.normalize(name);
 
         let point = lines
             .find_map(|x|{ 
-                let y = x.split_once(" ").unwrap(); 
-                if y.0 == name { Some(y.1) }
+                let y = descriptor_tools::parse_desc_index_line(x)?; 
+                if matching.normalize(y.0) == wanted { Some(y.1) }
                 else{ None }
             });
 
@@ -316,42 +536,117 @@ impl DescriptorStore for DescriptorStoreFS {
         }
         Descriptor::from(content)
     }
+
+    ///
+    /// Collects all Descriptors indexed for the point, compared by the point matching of the
+    /// space. The same desc_id indexed twice for a point is only returned once.
+    ///
+    fn get_descs_for_point(&self, point: &str) -> Vec<Descriptor> {
+        let binding = self.get_desc_point_indexes();
+        let matching = self.get_point_matching();
+        let wanted = matching.normalize(point);
+        let mut ids: Vec<&str> = Vec::new();
+        binding.lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .filter(|x| matching.normalize(x.0) == wanted)
+            .for_each(|x| if !ids.contains(&x.1) { ids.push(x.1) });
+
+        ids.iter().filter_map(|x| self.get_desc_by_id(x)).collect()
+    }
+
+    fn get_desc_by_id(&self, desc_id: &str) -> Option<Descriptor> {
+        let content = self.load_desc(desc_id);
+        if content.is_empty() {
+            return None;
+        }
+        let mut desc = Descriptor::from(content);
+        desc.set_desc_id(desc_id);
+        Some(desc)
+    }
+
+    ///
+    /// Reads the point policy from the metadata of the current space.
+    ///
+    fn get_point_policy(&self) -> PointPolicy {
+        self.get_current_space_info().point_policy
+    }
+
+    fn set_point_policy(&self, policy: PointPolicy) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
+        let mut info = self.get_current_space_info();
+        info.point_policy = policy;
+        self.write_space_info(&self.get_space_id(), &info)
+    }
+
+    fn set_point_matching(&self, matching: PointMatching) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
+        let mut info = self.get_current_space_info();
+        info.point_matching = matching;
+        self.write_space_info(&self.get_space_id(), &info)
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.check_space_writable(&self.get_space_id()).is_err()
+    }
     
 
     ///
     /// Method used to persist a Descriptor. 
     ///
-    fn add_desc(&self, desc: Descriptor, id: String) {
+    fn add_desc(&self, desc: Descriptor, id: String) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
         let description = String::from(desc.clone());
-        let path = self.desc_folder_path.clone();
-        let _ = path.join(id);
+        let path = self.desc_folder_path.join(id);
 
-        let _ = fs::write(path, description);
+        fs::write(path, description)?;
+        Ok(())
     }
 
 
+    ///
+    /// Removes the descriptor file and drops every index line pointing to it.
+    ///
+    fn remove_desc(&self, desc_id: &str) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
+        let without_desc = |indexes: String| -> String {
+            indexes.lines()
+                .filter(|x| descriptor_tools::parse_desc_index_line(x).map(|y| y.1) != Some(desc_id))
+                .collect::<Vec<&str>>()
+                .join("\n")
+        };
+        self.set_desc_point_indexes(&without_desc(self.get_desc_point_indexes()))?;
+        self.set_desc_name_indexes(&without_desc(self.get_desc_name_indexes()))?;
+        self.set_desc_label_indexes(&without_desc(self.get_desc_label_indexes()))?;
+        self.set_desc_description_indexes(&without_desc(self.get_desc_description_indexes()))?;
+
+        let _ = fs::remove_file(self.desc_folder_path.join(desc_id));
+        Ok(())
+    }
+
     ///
     /// Takes a descriptor note as argument and creates indexes for its variables. 
     /// It is important that the descriptor note has a desc_id. 
     ///
-    fn index_desc(&self, desc: Descriptor) {
+    fn index_desc(&self, desc: Descriptor) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
 
         let mut point_index = self.get_desc_point_indexes();
         point_index = Self::append_index(desc.desc_id.clone(), desc.point.clone(), point_index.clone());
-        self.set_desc_point_indexes(&point_index);
+        self.set_desc_point_indexes(&point_index)?;
 
         let mut name_index = self.get_desc_name_indexes();
         name_index = Self::append_index(desc.desc_id.clone(), desc.name.clone(), name_index.clone());
-        self.set_desc_name_indexes(&name_index);
+        self.set_desc_name_indexes(&name_index)?;
 
         let mut label_index = self.get_desc_label_indexes();
         label_index = Self::append_index(desc.desc_id.clone(), desc.label.clone(), label_index.clone());
-        self.set_desc_label_indexes(&label_index);
+        self.set_desc_label_indexes(&label_index)?;
 
         let mut desc_index = self.get_desc_description_indexes();
         desc_index = Self::append_index(desc.desc_id.clone(), desc.description.clone(), desc_index.clone());
-        self.set_desc_description_indexes(&desc_index);
+        self.set_desc_description_indexes(&desc_index)?;
 
+        Ok(())
     }
 
     ///
@@ -359,57 +654,88 @@ impl DescriptorStore for DescriptorStoreFS {
     ///
     fn get_desc_point_indexes(&self) -> String {
         let filename = self.get_index_path(DescIndex::DescPointIndex);    
-        fs::read_to_string(filename).expect("something wetn wrong reading the desc_point_index_file file")
+        fs::read_to_string(filename).unwrap_or_default()
+    }
+
+    fn iter_desc_point_indexes(&self) -> Box<dyn Iterator<Item = String>> {
+        match fs::File::open(self.get_index_path(DescIndex::DescPointIndex)) {
+            Ok(file) => Box::new(BufReader::new(file).lines().map_while(Result::ok)),
+            Err(_) => Box::new(std::iter::empty()),
+        }
     }
 
     fn get_desc_name_indexes(&self) -> String  {
 
         let filename = self.get_index_path(DescIndex::DescNameIndex);    
-        fs::read_to_string(filename).expect("something wetn wrong reading the desc_name_index_file file")
+        fs::read_to_string(filename).unwrap_or_default()
     }
 
     fn get_desc_label_indexes(&self) -> String  {
    
         let filename = self.get_index_path(DescIndex::DescLabelIndex);    
-        fs::read_to_string(filename).expect("something wetn wrong reading the desc_label_index_file file")
+        fs::read_to_string(filename).unwrap_or_default()
     }
 
     fn get_desc_description_indexes(&self) -> String  {
     
         let filename = self.get_index_path(DescIndex::DescDescIndex);    
-        fs::read_to_string(filename).expect("something wetn wrong reading the desc_description_index_file file")
+        fs::read_to_string(filename).unwrap_or_default()
+    }
+
+    ///
+    /// Hashes the space id with the size and modification time of the index files and the
+    /// space settings, without reading them.
+    ///
+    fn get_index_fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.get_space_id().hash(&mut hasher);
+        let indexes = [DescIndex::DescPointIndex, DescIndex::DescNameIndex, DescIndex::DescLabelIndex, DescIndex::DescDescIndex];
+        let paths = indexes
+            .into_iter()
+            .map(|x| self.get_index_path(x))
+            .chain([self.get_space_info_path(&self.get_space_id())]);
+        for path in paths {
+            if let Ok(meta) = fs::metadata(path) {
+                meta.len().hash(&mut hasher);
+                meta.modified().ok().hash(&mut hasher);
+            }
+        }
+        hasher.finish()
     }
 
     ///
     /// This method returns all indexing records of descriptors based on the point field, for the
     /// space specified with the method parameter space_id. 
-    /// The method makes a temporary switch to the new space_id and then reverts the object back to
-    /// its original space_id.
+    /// The space in use is left untouched.
     ///
-    fn get_tmp_space_desc_point_indexes(&mut self, space_id: String) -> String {
+    fn get_tmp_space_desc_point_indexes(&self, space_id: SpaceId) -> String {
 
-        self.set_tmp_space_id(space_id);
-        let filename = self.get_index_path(DescIndex::DescPointIndex);
-        self.revert_space_id();
-        fs::read_to_string(filename).expect("something wetn wrong reading the desc_point_index_file file")
+        self.view_space(&space_id).get_desc_point_indexes()
     }
 
 
-    fn set_desc_point_indexes(&self, lines: &str) { 
-    
+    fn set_desc_point_indexes(&self, lines: &str) -> Result<(), DescError> { 
+        self.check_space_writable(&self.get_space_id())?;
         file_tools::write(self.get_index_path(descriptor_facade::DescIndex::DescPointIndex), lines);
+        Ok(())
     }
 
-    fn set_desc_name_indexes(&self, lines: &str){
+    fn set_desc_name_indexes(&self, lines: &str) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
         file_tools::write(self.get_index_path(descriptor_facade::DescIndex::DescNameIndex), lines);
+        Ok(())
     }
 
-    fn set_desc_label_indexes(&self, lines: &str) { 
+    fn set_desc_label_indexes(&self, lines: &str) -> Result<(), DescError> { 
+        self.check_space_writable(&self.get_space_id())?;
         file_tools::write(self.get_index_path(descriptor_facade::DescIndex::DescLabelIndex), lines);
+        Ok(())
     }
 
-    fn set_desc_description_indexes(&self, lines:&str) {
+    fn set_desc_description_indexes(&self, lines:&str) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
         file_tools::write(self.get_index_path(descriptor_facade::DescIndex::DescDescIndex), lines);
+        Ok(())
     }
 
 }
@@ -0,0 +1,350 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard};
+use crate::{Descriptor, DescError, PointMatching, PointPolicy, SpaceId, SpaceInfo, SpaceSummary};
+use crate::misc::descriptor_tools;
+use super::descriptor_store::DescriptorStore;
+
+const POINT_INDEX: usize = 0;
+const NAME_INDEX: usize = 1;
+const LABEL_INDEX: usize = 2;
+const DESCRIPTION_INDEX: usize = 3;
+
+#[derive(Clone, Default)]
+struct MemorySpace {
+    info: SpaceInfo,
+    descs: HashMap<String, String>,
+    indexes: [String; 4],
+}
+
+///
+/// A DescriptorStore holding its spaces in memory, for tests. Descriptors and index lines are
+/// kept the way DescriptorStoreFS writes them to files. Clones and views share the spaces.
+///
+#[derive(Clone)]
+pub(crate) struct MemoryStore {
+    spaces: Arc<Mutex<HashMap<SpaceId, MemorySpace>>>,
+    failing_removals: Arc<Mutex<HashSet<SpaceId>>>,
+    org_space: SpaceId,
+    tmp_space: Option<SpaceId>,
+}
+
+impl MemoryStore {
+
+    pub(crate) fn new(space_id: SpaceId) -> Self {
+        let store = MemoryStore {
+            spaces: Arc::new(Mutex::new(HashMap::new())),
+            failing_removals: Arc::new(Mutex::new(HashSet::new())),
+            org_space: space_id,
+            tmp_space: None,
+        };
+        store.ensure_space();
+        store
+    }
+
+    ///
+    /// Makes remove_desc fail with DescError::Io in the space from now on.
+    ///
+    pub(crate) fn fail_removals(&self, space_id: &SpaceId) {
+        self.failing_removals.lock().unwrap().insert(space_id.clone());
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<SpaceId, MemorySpace>> {
+        self.spaces.lock().unwrap_or_else(|x| x.into_inner())
+    }
+
+    fn ensure_space(&self) {
+        self.lock().entry(self.get_space_id()).or_default();
+    }
+
+    fn read<R>(&self, f: impl FnOnce(&MemorySpace) -> R) -> R {
+        let spaces = self.lock();
+        match spaces.get(&self.get_space_id()) {
+            Some(space) => f(space),
+            None => f(&MemorySpace::default()),
+        }
+    }
+
+    fn write<R>(&self, f: impl FnOnce(&mut MemorySpace) -> R) -> Result<R, DescError> {
+        self.check_space_writable(&self.get_space_id())?;
+        let mut spaces = self.lock();
+        Ok(f(spaces.entry(self.get_space_id()).or_default()))
+    }
+
+    fn check_space_writable(&self, space_id: &SpaceId) -> Result<(), DescError> {
+        if self.get_space_info(space_id).is_some_and(|x| x.read_only) {
+            return Err(DescError::ReadOnly(space_id.to_string()));
+        }
+        Ok(())
+    }
+
+    fn is_space_in_use(&self, space_id: &SpaceId) -> bool {
+        self.get_space_id() == *space_id || self.org_space == *space_id
+    }
+
+    fn get_index(&self, index: usize) -> String {
+        self.read(|x| x.indexes[index].clone())
+    }
+
+    fn set_index(&self, index: usize, lines: &str) -> Result<(), DescError> {
+        self.write(|x| x.indexes[index] = lines.to_string())
+    }
+
+    fn append_index(index: &mut String, id: &str, value: &str) {
+        if !index.is_empty() {
+            index.push('\n');
+        }
+        index.push_str(&value.trim().replace(['\n', '\r'], " "));
+        index.push(' ');
+        index.push_str(id);
+    }
+
+    fn find_desc_id(&self, point: &str) -> Option<String> {
+        let matching = self.get_point_matching();
+        let wanted = matching.normalize(point);
+        self.get_desc_point_indexes()
+            .lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .find(|x| matching.normalize(x.0) == wanted)
+            .map(|x| x.1.to_string())
+    }
+}
+
+impl DescriptorStore for MemoryStore {
+
+    fn get_desc(&self, name: &str) -> Descriptor {
+        self.find_desc_id(name).and_then(|x| self.get_desc_by_id(&x)).unwrap_or_default()
+    }
+
+    fn get_descs(&self, points: Vec<&str>) -> Vec<Descriptor> {
+        points.iter().map(|x| self.get_desc(x)).collect()
+    }
+
+    fn get_desc_or_id(&self, name: &str) -> Descriptor {
+        self.find_desc_id(name)
+            .and_then(|x| self.get_desc_by_id(&x))
+            .unwrap_or_else(|| Descriptor { point: name.to_string(), ..Default::default() })
+    }
+
+    fn get_descs_or_else_ids(&self, points: Vec<String>) -> Vec<Descriptor> {
+        points.iter().map(|x| self.get_desc_or_id(x)).collect()
+    }
+
+    fn get_all_descs(&self) -> Vec<Descriptor> {
+        self.get_desc_point_indexes()
+            .lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .filter_map(|x| self.get_desc_by_id(x.1))
+            .collect()
+    }
+
+    fn get_descs_for_point(&self, point: &str) -> Vec<Descriptor> {
+        let matching = self.get_point_matching();
+        let wanted = matching.normalize(point);
+        let indexes = self.get_desc_point_indexes();
+        let mut ids: Vec<&str> = Vec::new();
+        indexes.lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .filter(|x| matching.normalize(x.0) == wanted)
+            .for_each(|x| if !ids.contains(&x.1) { ids.push(x.1) });
+        ids.iter().filter_map(|x| self.get_desc_by_id(x)).collect()
+    }
+
+    fn get_desc_by_id(&self, desc_id: &str) -> Option<Descriptor> {
+        let content = self.read(|x| x.descs.get(desc_id).cloned())?;
+        let mut desc = Descriptor::from(content);
+        desc.set_desc_id(desc_id);
+        Some(desc)
+    }
+
+    fn add_desc(&self, desc: Descriptor, id: String) -> Result<(), DescError> {
+        self.write(|x| x.descs.insert(id, String::from(desc))).map(|_| ())
+    }
+
+    fn remove_desc(&self, desc_id: &str) -> Result<(), DescError> {
+        self.check_space_writable(&self.get_space_id())?;
+        if self.failing_removals.lock().unwrap().contains(&self.get_space_id()) {
+            return Err(DescError::Io(format!("can not remove {}", desc_id)));
+        }
+        self.write(|space| {
+            for index in space.indexes.iter_mut() {
+                *index = index.lines()
+                    .filter(|x| descriptor_tools::parse_desc_index_line(x).map(|y| y.1) != Some(desc_id))
+                    .collect::<Vec<&str>>()
+                    .join("\n");
+            }
+            space.descs.remove(desc_id);
+        })
+    }
+
+    fn get_desc_point_indexes(&self) -> String {
+        self.get_index(POINT_INDEX)
+    }
+
+    fn get_tmp_space_desc_point_indexes(&self, space_id: SpaceId) -> String {
+        self.view_space(&space_id).get_desc_point_indexes()
+    }
+
+    fn get_desc_name_indexes(&self) -> String {
+        self.get_index(NAME_INDEX)
+    }
+
+    fn get_desc_label_indexes(&self) -> String {
+        self.get_index(LABEL_INDEX)
+    }
+
+    fn get_desc_description_indexes(&self) -> String {
+        self.get_index(DESCRIPTION_INDEX)
+    }
+
+    fn get_index_fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.get_space_id().hash(&mut hasher);
+        self.read(|x| {
+            x.indexes.hash(&mut hasher);
+            serde_json::to_string(&x.info).unwrap_or_default().hash(&mut hasher);
+        });
+        hasher.finish()
+    }
+
+    fn set_desc_point_indexes(&self, indexes: &str) -> Result<(), DescError> {
+        self.set_index(POINT_INDEX, indexes)
+    }
+
+    fn set_desc_name_indexes(&self, indexes: &str) -> Result<(), DescError> {
+        self.set_index(NAME_INDEX, indexes)
+    }
+
+    fn set_desc_label_indexes(&self, indexes: &str) -> Result<(), DescError> {
+        self.set_index(LABEL_INDEX, indexes)
+    }
+
+    fn set_desc_description_indexes(&self, indexes: &str) -> Result<(), DescError> {
+        self.set_index(DESCRIPTION_INDEX, indexes)
+    }
+
+    fn set_tmp_space_id(&mut self, space_id: SpaceId) {
+        self.tmp_space = Some(space_id);
+        self.ensure_space();
+    }
+
+    fn revert_space_id(&mut self) {
+        self.tmp_space = None;
+        self.ensure_space();
+    }
+
+    fn get_space_id(&self) -> SpaceId {
+        self.tmp_space.clone().unwrap_or_else(|| self.org_space.clone())
+    }
+
+    fn get_tmp_space_id(&self) -> Option<SpaceId> {
+        self.tmp_space.clone()
+    }
+
+    fn view_space(&self, space_id: &SpaceId) -> Self {
+        let mut view = self.clone();
+        view.tmp_space = Some(space_id.clone());
+        view
+    }
+
+    fn get_point_policy(&self) -> PointPolicy {
+        self.get_current_space_info().point_policy
+    }
+
+    fn set_point_policy(&self, policy: PointPolicy) -> Result<(), DescError> {
+        self.write(|x| x.info.point_policy = policy)
+    }
+
+    fn set_point_matching(&self, matching: PointMatching) -> Result<(), DescError> {
+        self.write(|x| x.info.point_matching = matching)
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.check_space_writable(&self.get_space_id()).is_err()
+    }
+
+    fn list_spaces(&self) -> Vec<SpaceId> {
+        let mut spaces: Vec<SpaceId> = self.lock().keys().cloned().collect();
+        spaces.sort();
+        spaces
+    }
+
+    fn get_space_info(&self, space_id: &SpaceId) -> Option<SpaceInfo> {
+        self.lock().get(space_id).map(|x| x.info.clone())
+    }
+
+    fn get_space_summary(&self, space_id: &SpaceId) -> Option<SpaceSummary> {
+        let space = self.lock().get(space_id).cloned()?;
+        let entries: Vec<(&str, &str)> = space.indexes[POINT_INDEX]
+            .lines()
+            .filter_map(descriptor_tools::parse_desc_index_line)
+            .collect();
+        Some(SpaceSummary {
+            space_id: space_id.clone(),
+            desc_count: entries.iter().map(|x| x.1).collect::<HashSet<&str>>().len(),
+            point_count: entries.iter().map(|x| space.info.point_matching.normalize(x.0)).collect::<HashSet<String>>().len(),
+            info: space.info,
+        })
+    }
+
+    fn create_space(&self, space_id: SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        let mut spaces = self.lock();
+        if spaces.contains_key(&space_id) {
+            return Err(DescError::SpaceAlreadyExists(space_id.to_string()));
+        }
+        spaces.insert(space_id, MemorySpace { info, ..Default::default() });
+        Ok(())
+    }
+
+    fn set_space_info(&self, space_id: &SpaceId, info: SpaceInfo) -> Result<(), DescError> {
+        match self.lock().get_mut(space_id) {
+            Some(space) => {
+                space.info = info;
+                Ok(())
+            }
+            None => Err(DescError::SpaceNotFound(space_id.to_string())),
+        }
+    }
+
+    fn rename_space(&self, space_id: SpaceId, new_space_id: SpaceId) -> Result<(), DescError> {
+        if self.get_space_info(&space_id).is_none() {
+            return Err(DescError::SpaceNotFound(space_id.to_string()));
+        }
+        if self.get_space_info(&new_space_id).is_some() {
+            return Err(DescError::SpaceAlreadyExists(new_space_id.to_string()));
+        }
+        if self.is_space_in_use(&space_id) {
+            return Err(DescError::SpaceInUse(space_id.to_string()));
+        }
+        self.check_space_writable(&space_id)?;
+        let mut spaces = self.lock();
+        if let Some(space) = spaces.remove(&space_id) {
+            spaces.insert(new_space_id, space);
+        }
+        Ok(())
+    }
+
+    fn delete_space(&self, space_id: SpaceId, confirmation: String) -> Result<(), DescError> {
+        if self.get_space_info(&space_id).is_none() {
+            return Err(DescError::SpaceNotFound(space_id.to_string()));
+        }
+        if confirmation != space_id.as_str() {
+            return Err(DescError::ConfirmationMismatch(space_id.to_string()));
+        }
+        if self.is_space_in_use(&space_id) {
+            return Err(DescError::SpaceInUse(space_id.to_string()));
+        }
+        self.check_space_writable(&space_id)?;
+        self.lock().remove(&space_id);
+        Ok(())
+    }
+
+    fn index_desc(&self, desc: Descriptor) -> Result<(), DescError> {
+        self.write(|space| {
+            let values = [&desc.point, &desc.name, &desc.label, &desc.description];
+            for (index, value) in space.indexes.iter_mut().zip(values) {
+                Self::append_index(index, &desc.desc_id, value);
+            }
+        })
+    }
+}
@@ -1,3 +1,7 @@
+pub mod completion_index;
 pub mod descriptor_facade;
 pub mod descriptor_store;
 pub mod descriptor_store_fs;
+#[cfg(test)]
+pub(crate) mod memory_store;
+pub mod space_guard;
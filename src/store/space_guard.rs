@@ -0,0 +1,90 @@
+use std::ops::{Deref, DerefMut};
+use super::descriptor_store::DescriptorStore;
+use crate::SpaceId;
+
+///
+/// Switches a DescriptorStore, or a layer on top of one, to a temporary space for as long as the
+/// guard lives.
+///
+/// When the guard is dropped, also on early return or while unwinding from a panic, the temporary
+/// space that was set before is restored, or the original space when there was none. Guards may
+/// be nested.
+///
+pub struct SpaceGuard<'a, T> {
+    target: &'a mut T,
+    previous_tmp_space_id: Option<SpaceId>,
+    restore: fn(&mut T, Option<SpaceId>),
+}
+
+impl<'a, T: DescriptorStore> SpaceGuard<'a, T> {
+
+    pub fn new(store: &'a mut T, space_id: SpaceId) -> Self {
+        let previous_tmp_space_id = store.get_tmp_space_id();
+        store.set_tmp_space_id(space_id);
+        SpaceGuard::with_restore(store, previous_tmp_space_id, |store, previous| match previous {
+            Some(space_id) => store.set_tmp_space_id(space_id),
+            None => store.revert_space_id(),
+        })
+    }
+}
+
+impl<'a, T> SpaceGuard<'a, T> {
+
+    ///
+    /// Guards a target that was already switched. On drop restore is called with the temporary
+    /// space id the target had before.
+    ///
+    pub(crate) fn with_restore(target: &'a mut T, previous_tmp_space_id: Option<SpaceId>, restore: fn(&mut T, Option<SpaceId>)) -> Self {
+        SpaceGuard { target, previous_tmp_space_id, restore }
+    }
+}
+
+impl<T> Deref for SpaceGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.target
+    }
+}
+
+impl<T> DerefMut for SpaceGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.target
+    }
+}
+
+impl<T> Drop for SpaceGuard<'_, T> {
+    fn drop(&mut self) {
+        (self.restore)(self.target, self.previous_tmp_space_id.take());
+    }
+}
+
+#[test]
+fn space_guard_restores_after_panic_test() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    let space = |x: &str| SpaceId::new(x).unwrap();
+    let mut store = super::memory_store::MemoryStore::new(space("org"));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        store.with_space(space("other"), |x| {
+            assert_eq!(x.get_space_id(), space("other"));
+            panic!("inside the space");
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(store.get_tmp_space_id(), None);
+
+    store.set_tmp_space_id(space("outer"));
+    {
+        let mut outer = store.enter_space(space("a"));
+        let inner = outer.enter_space(space("b"));
+        assert_eq!(inner.get_space_id(), space("b"));
+    }
+    assert_eq!(store.get_tmp_space_id(), Some(space("outer")));
+
+    let mut facade = crate::descriptor_facade::DescriptorFacade::new(super::memory_store::MemoryStore::new(space("org")));
+    let result = catch_unwind(AssertUnwindSafe(|| facade.with_space(space("other"), |_| panic!("inside the space"))));
+    assert!(result.is_err());
+    assert_eq!(facade.get_tmp_space_id(), None);
+    assert_eq!(facade.get_space_id(), space("org"));
+}